@@ -1,8 +1,63 @@
 use super::*;
+use std::fmt;
 
 #[derive(Clone, Debug)]
 pub enum DefineMember<'a> {
     Member(String),
     Number(f64),
-    Tuple(Box<ASTNode<'a>>),
+    Tuple(Destructure<'a>),
+}
+
+/// The target of a destructuring definition, ie. `(a, (b, _))` in `(a, (b, _)): value`
+#[derive(Clone, Debug)]
+pub enum Destructure<'a> {
+    Ignore,
+    Variable(String, Location<'a>),
+    Tuple(Vec<Destructure<'a>>, Location<'a>),
+}
+
+impl<'a> Destructure<'a> {
+    pub fn from_node(node: &ASTNode<'a>, location: &Location<'a>) -> Destructure<'a> {
+        //! Reads a destructuring target out of a parsed tuple, whose elements must be names, `_` or nested tuples
+        match node {
+            ASTNode::VoidSymbol => Destructure::Ignore,
+            ASTNode::Variable(name) => Destructure::Variable(name.clone(), location.clone()),
+            ASTNode::Tuple(ast, false) => Destructure::Tuple(
+                ast.instructions
+                    .iter()
+                    .map(|(node, location)| Destructure::from_node(node, location))
+                    .collect(),
+                location.clone(),
+            ),
+            _ => CompError::new(
+                108,
+                String::from("Invalid destructuring target: expected a name, `_` or a tuple of them"),
+                CompLocation::from(location),
+            )
+            .print_and_exit(),
+        }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        //! Returns the names bound by the target, in order
+        match self {
+            Destructure::Ignore => Vec::new(),
+            Destructure::Variable(name, _) => vec![name.clone()],
+            Destructure::Tuple(elements, _) => elements.iter().flat_map(|x| x.names()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for Destructure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Destructure::Ignore => write!(f, "_"),
+            Destructure::Variable(name, _) => write!(f, "{}", name),
+            Destructure::Tuple(elements, _) => write!(
+                f,
+                "({})",
+                elements.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
 }
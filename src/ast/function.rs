@@ -3,11 +3,13 @@ use super::*;
 /// The Function type, corresponds to `TUPLE ARROW BLOCK`
 #[derive(Debug, Clone)]
 pub struct Function<'a> {
-    pub args: Vec<FunctionArg>,
+    pub args: Vec<FunctionArg<'a>>,
+    pub rest: Option<String>,
     pub body: AST<'a>,
     pub has_self: bool,
     pub has_lhs: bool,
     pub has_new: bool,
+    pub has_super: bool,
     pub closure: Vec<(String, AST<'a>)>,
     pub refs: Vec<(String, Location<'a>)>,
 }
@@ -30,19 +32,50 @@ impl<'a> Function<'a> {
                 let mut has_self = false;
                 let mut has_lhs = false;
                 let mut has_new = false;
+                let mut has_super = false;
                 let mut args = Vec::<FunctionArg>::new();
+                let mut rest: Option<String> = None;
                 let mut visited = Vec::<(ASTNode, Location)>::new();
                 let mut closure = Vec::<(String, AST)>::new();
                 let mut refs = Vec::<(String, Location)>::new();
                 for (raw_arg, location) in tuple.instructions {
+                    if let ASTNode::Variable(_)
+                    | ASTNode::TypedVariable(_, _)
+                    | ASTNode::VariableDef(_, _)
+                    | ASTNode::Tuple(_, false) = &raw_arg
+                    {
+                        check_arg_position(&raw_arg, &args, &rest, &location);
+                    }
                     match &raw_arg {
                         ASTNode::Variable(name) => args.push(FunctionArg {
                             argtype: None,
                             name: name.to_string(),
+                            default: None,
+                            pattern: None,
                         }),
+                        ASTNode::Tuple(_, false) => {
+                            let pattern = Destructure::from_node(&raw_arg, &location);
+                            args.push(FunctionArg {
+                                argtype: None,
+                                name: pattern.to_string(),
+                                default: None,
+                                pattern: Some(pattern),
+                            })
+                        }
                         ASTNode::TypedVariable(name, argtype) => args.push(FunctionArg {
                             argtype: Some(argtype.clone()),
                             name: name.to_string(),
+                            default: None,
+                            pattern: None,
+                        }),
+                        ASTNode::VariableDef(name, value) => args.push(FunctionArg {
+                            argtype: None,
+                            name: name.to_string(),
+                            default: Some(AST {
+                                instructions: vec![(value.as_ref().clone(), location.clone())],
+                                kind: ASTKind::Block,
+                            }),
+                            pattern: None,
                         }),
                         ASTNode::PatternCall(name, args) => {
                             // TODO: match?
@@ -82,6 +115,21 @@ impl<'a> Function<'a> {
                                 } else {
                                     has_new = true;
                                 }
+                            } else if name == "#super" {
+                                if has_super {
+                                    error_double_flag(name, visited, location);
+                                } else if !is_pattern {
+                                    CompError::new(
+                                        105,
+                                        String::from(
+                                            "#super() can only be used as a pattern's argument",
+                                        ),
+                                        CompLocation::from(&location),
+                                    )
+                                    .print_and_exit();
+                                } else {
+                                    has_super = true;
+                                }
                             } else if name == "#with" {
                                 if args.instructions.len() == 1 {
                                     if let (ASTNode::Variable(name), loc) = &args.instructions[0] {
@@ -125,6 +173,26 @@ impl<'a> Function<'a> {
                                         location.into()
                                     ).print_and_exit();
                                 }
+                            } else if name == "#rest" {
+                                if rest.is_some() {
+                                    error_double_flag(name, visited, location);
+                                } else if args.instructions.len() == 1 {
+                                    if let (ASTNode::Variable(name), _) = &args.instructions[0] {
+                                        rest = Some(name.clone());
+                                    } else {
+                                        CompError::new(
+                                            12,
+                                            String::from("Invalid argument in function definition: #rest(name) takes as argument a variable."),
+                                            location.into()
+                                        ).print_and_exit();
+                                    }
+                                } else {
+                                    CompError::new(
+                                        12,
+                                        String::from("Invalid argument in function definition: #rest(name) takes exactly one argument."),
+                                        location.into()
+                                    ).print_and_exit();
+                                }
                             } else if name == "#ref" {
                                 if args.instructions.len() == 1 {
                                     if let (ASTNode::Variable(name), loc) = &args.instructions[0] {
@@ -164,10 +232,12 @@ impl<'a> Function<'a> {
                 }
                 Some(Function {
                     args,
+                    rest,
                     body,
                     has_self,
                     has_lhs,
                     has_new,
+                    has_super,
                     closure,
                     refs,
                 })
@@ -178,9 +248,44 @@ impl<'a> Function<'a> {
 }
 
 #[derive(Debug, Clone)]
-pub struct FunctionArg {
+pub struct FunctionArg<'a> {
     pub argtype: Option<Type>,
     pub name: String,
+    pub default: Option<AST<'a>>,
+    /// Set for tuples of names, which destructure the argument, ie. `((a, b)) => {}`
+    pub pattern: Option<Destructure<'a>>,
+}
+
+fn check_arg_position(
+    arg: &ASTNode<'_>,
+    args: &[FunctionArg<'_>],
+    rest: &Option<String>,
+    location: &Location<'_>,
+) {
+    //! Verifies that `arg` may follow the previous arguments: arguments without default value cannot follow ones with a default value and no argument can follow `#rest()`
+    if let Some(rest) = rest {
+        CompError::new(
+            12,
+            String::from("Invalid argument in function definition: arguments cannot follow #rest()"),
+            location.into(),
+        )
+        .append(
+            format!("Consider moving #rest({}) at the end of the arguments", rest),
+            CompLocation::None,
+        )
+        .print_and_exit();
+    }
+    if let ASTNode::VariableDef(_, _) = arg {
+        return;
+    }
+    if args.iter().any(|arg| arg.default.is_some()) {
+        CompError::new(
+            12,
+            String::from("Invalid argument in function definition: arguments without default value cannot follow arguments with a default value"),
+            location.into(),
+        )
+        .print_and_exit();
+    }
 }
 
 fn error_double_flag(
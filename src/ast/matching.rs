@@ -0,0 +1,190 @@
+use super::*;
+
+/// A `#match(value, pattern => {body}, ...)` construct
+#[derive(Debug, Clone)]
+pub struct Match<'a> {
+    pub value: AST<'a>,
+    pub arms: Vec<MatchArm<'a>>,
+}
+
+/// An arm of a `#match`: `pattern #if(guard) => {body}`, whose guard is optional
+#[derive(Debug, Clone)]
+pub struct MatchArm<'a> {
+    pub pattern: MatchPattern<'a>,
+    pub guard: Option<AST<'a>>,
+    pub body: AST<'a>,
+    pub location: Location<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MatchPattern<'a> {
+    /// `_`, matches anything
+    Wildcard,
+    /// A number, string or boolean, matching equal values
+    Literal(ASTNode<'a>),
+    /// `name`, matches anything and binds it to `name`
+    Bind(String),
+    /// `(a, _, 1)`, matches tuples of the same length whose elements match
+    Tuple(Vec<MatchPattern<'a>>),
+    /// `Point`, `Circle(r)` or `Point {x, y: 0}`, matches instances of the struct (or of the structs conforming to it) whose fields match
+    Struct(TypeName, StructPatternFields<'a>, Location<'a>),
+}
+
+#[derive(Debug, Clone)]
+pub enum StructPatternFields<'a> {
+    Any,
+    /// The fields of an enum variant, in order
+    Positional(Vec<MatchPattern<'a>>),
+    Named(Vec<(String, MatchPattern<'a>)>),
+}
+
+type Tokens<'b, 'a> = &'b [(Token<'a>, Location<'a>)];
+
+impl<'a> Match<'a> {
+    /** Parses the argument tuple of `#match`: the matched value, followed by the arms, separated by commas or newlines.
+        @param location - The location of `#match`, for errors
+    **/
+    pub fn parse(tuple: TokenTree<'a>, location: &Location<'a>) -> Match<'a> {
+        let tokens = &tuple.tokens[..];
+        let value_end = tokens
+            .iter()
+            .position(|(token, _)| matches!(token, Token::Separator))
+            .unwrap_or_else(|| {
+                CompError::new(
+                    24,
+                    String::from("Invalid #match: expected a value followed by arms"),
+                    CompLocation::from(location),
+                )
+                .print_and_exit()
+            });
+        let mut value = TokenTree::new(tuple.kind, tuple.start_loc.clone());
+        value.tokens = tokens[..value_end].to_vec();
+
+        let mut arms = Vec::new();
+        let mut offset = value_end + 1;
+        while offset < tokens.len() {
+            if let (Token::Separator, _) = &tokens[offset] {
+                offset += 1;
+                continue;
+            }
+            arms.push(MatchArm::parse(tokens, &mut offset));
+        }
+
+        Match {
+            value: AST::parse(value, ASTKind::Block),
+            arms,
+        }
+    }
+}
+
+impl<'a> MatchArm<'a> {
+    fn parse(tokens: Tokens<'_, 'a>, offset: &mut usize) -> MatchArm<'a> {
+        //! Parses the arm starting at `offset`, which is moved past its body
+        let location = tokens[*offset].1.clone();
+        let pattern_end = tokens[*offset..]
+            .iter()
+            .position(|(token, _)| match token {
+                Token::Arrow => true,
+                Token::Pattern(name) => name == "#if",
+                _ => false,
+            })
+            .map(|len| *offset + len)
+            .unwrap_or_else(|| arm_error("expected `=>` following the pattern", &location));
+        let pattern = MatchPattern::parse(&tokens[*offset..pattern_end], &location);
+        *offset = pattern_end;
+
+        let guard = match &tokens[*offset..] {
+            [(Token::Pattern(_), _), (Token::Tuple(guard), _), ..] => {
+                *offset += 2;
+                Some(AST::parse(guard.clone(), ASTKind::Block))
+            }
+            [(Token::Pattern(_), loc), ..] => arm_error("expected a condition following #if", loc),
+            _ => None,
+        };
+
+        match &tokens[*offset..] {
+            [(Token::Arrow, _), (Token::Block(body), _), ..] => {
+                *offset += 2;
+                MatchArm {
+                    pattern,
+                    guard,
+                    body: AST::parse(body.clone(), ASTKind::Block),
+                    location,
+                }
+            }
+            [(_, loc), ..] => arm_error("expected `=> {body}` following the pattern", loc),
+            [] => arm_error("expected `=> {body}` following the pattern", &location),
+        }
+    }
+}
+
+impl<'a> MatchPattern<'a> {
+    fn parse(tokens: Tokens<'_, 'a>, location: &Location<'a>) -> MatchPattern<'a> {
+        //! Parses a pattern out of `tokens`; `location` is that of the arm, for errors
+        match tokens {
+            [(Token::VoidSymbol, _)] => MatchPattern::Wildcard,
+            [(Token::Symbol(name), _)] => MatchPattern::Bind(name.clone()),
+            [(Token::Number(x), _)] => MatchPattern::Literal(ASTNode::Number(*x)),
+            [(Token::String(x), _)] => MatchPattern::Literal(ASTNode::String(x.clone())),
+            [(Token::Boolean(x), _)] => MatchPattern::Literal(ASTNode::Boolean(*x)),
+            [(Token::Tuple(tuple), loc)] => {
+                MatchPattern::Tuple(MatchPattern::parse_list(&tuple.tokens, loc))
+            }
+            [(Token::TypeName(name), loc)] => {
+                MatchPattern::Struct(name.clone(), StructPatternFields::Any, loc.clone())
+            }
+            [(Token::TypeName(name), loc), (Token::Tuple(fields), _)] => MatchPattern::Struct(
+                name.clone(),
+                StructPatternFields::Positional(MatchPattern::parse_list(&fields.tokens, loc)),
+                loc.clone(),
+            ),
+            [(Token::TypeName(name), loc), (Token::Block(fields), _)] => MatchPattern::Struct(
+                name.clone(),
+                StructPatternFields::Named(MatchPattern::parse_fields(&fields.tokens, loc)),
+                loc.clone(),
+            ),
+            [(_, loc), ..] => arm_error(
+                "expected a literal, `_`, a name, a tuple or a struct as pattern",
+                loc,
+            ),
+            [] => arm_error("expected a pattern", location),
+        }
+    }
+
+    fn parse_list(tokens: Tokens<'_, 'a>, location: &Location<'a>) -> Vec<MatchPattern<'a>> {
+        //! Parses the comma-separated patterns of a tuple
+        tokens
+            .split(|(token, _)| matches!(token, Token::Separator))
+            .filter(|element| !element.is_empty())
+            .map(|element| MatchPattern::parse(element, location))
+            .collect()
+    }
+
+    fn parse_fields(
+        tokens: Tokens<'_, 'a>,
+        location: &Location<'a>,
+    ) -> Vec<(String, MatchPattern<'a>)> {
+        //! Parses the fields of a struct pattern: `name` binds the field to its own name, while `name: pattern` matches it against `pattern`
+        tokens
+            .split(|(token, _)| matches!(token, Token::Separator))
+            .filter(|field| !field.is_empty())
+            .map(|field| match field {
+                [(Token::Symbol(name), _)] => (name.clone(), MatchPattern::Bind(name.clone())),
+                [(Token::Symbol(name), _), (Token::Define, _), pattern @ ..] => {
+                    (name.clone(), MatchPattern::parse(pattern, location))
+                }
+                [(_, loc), ..] => arm_error("expected `field` or `field: pattern` in struct pattern", loc),
+                [] => unreachable!(),
+            })
+            .collect()
+    }
+}
+
+fn arm_error(msg: &str, location: &Location<'_>) -> ! {
+    CompError::new(
+        24,
+        format!("Invalid #match arm: {}", msg),
+        CompLocation::from(location),
+    )
+    .print_and_exit()
+}
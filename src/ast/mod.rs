@@ -1,9 +1,11 @@
 pub mod define;
 pub mod expr;
 pub mod function;
+pub mod matching;
 pub mod node;
 pub mod pattern;
 pub mod resolve;
+pub mod types;
 
 pub use super::error::*;
 pub use super::parser::{
@@ -14,9 +16,11 @@ pub use crate::Location;
 pub use define::*;
 pub use expr::*;
 pub use function::*;
+pub use matching::*;
 pub use node::ASTNode;
 pub use pattern::*;
 pub use resolve::*;
+pub use types::*;
 pub use std::rc::Rc;
 
 /** Asyntactical tree: a more tree-like representation of instructions and expressions
@@ -45,8 +49,20 @@ impl<'a> AST<'a> {
         let mut instructions = Vec::<(ASTNode<'a>, Location<'a>)>::new();
         let mut offset = 0usize;
         while offset < len {
+            // doc comments (`///`) are attached to the declaration following them, and discarded otherwise
+            let mut docs: Vec<&str> = Vec::new();
+            while let Some((Token::DocComment(doc), _)) = raw.tokens.get(offset) {
+                docs.push(doc);
+                offset += 1;
+            }
+            if offset >= len {
+                break;
+            }
             match construct::construct(raw.clone(), &mut offset) {
-                Some(node) => {
+                Some(mut node) => {
+                    if !docs.is_empty() {
+                        node.0.set_doc(docs.join("\n"));
+                    }
                     kind.verify_term(&node);
                     instructions.push(node);
                     expect_next_instruction(raw.clone(), &mut offset);
@@ -139,12 +155,13 @@ fn expect_next_instruction<'a>(tree: Rc<TokenTree<'a>>, offset: &mut usize) {
     let old_loc = &tree.tokens[*offset - 1].1;
     let new_loc = &tree.tokens[*offset].1;
 
-    if old_loc.line < new_loc.line {
+    // the separator is checked first, as terms spanning several lines (blocks, strings) are located on their first line
+    if let (Token::Separator, _) = tree.tokens[*offset] {
+        *offset += 1;
         return;
     }
 
-    if let (Token::Separator, _) = tree.tokens[*offset] {
-        *offset += 1;
+    if old_loc.line < new_loc.line {
         return;
     }
 
@@ -3,10 +3,11 @@ use super::*;
 /// A node in an AST
 #[derive(Debug, Clone)]
 pub enum ASTNode<'a> {
-    Function(Function<'a>),
+    Function(Box<Function<'a>>),
     PatternDecl(Pattern<'a>),
     PatternCall(String, AST<'a>), // name, tuple
     MethodCall(String, AST<'a>),
+    Match(Box<Match<'a>>),
     Member(String),
     Pattern(String),
     Variable(String),
@@ -14,38 +15,77 @@ pub enum ASTNode<'a> {
     TypeName(token::TypeName),
     VoidSymbol,
     VariableDecl(String),
-    VariableInit(String, Box<ASTNode<'a>>),
+    VariableInit(String, Box<ASTNode<'a>>, Option<String>), // name, value, doc
+    ConstInit(String, Box<ASTNode<'a>>, Option<String>),    // name, value, doc
+    StaticInit(String, Box<ASTNode<'a>>, Option<String>),   // name, value, doc
     VariableDef(String, Box<ASTNode<'a>>),
+    DestructureInit(Destructure<'a>, Box<ASTNode<'a>>), // target, value
+    NamedArg(String, Box<ASTNode<'a>>), // name, value
     ComplexDef(Expression<'a>, DefineMember<'a>, Box<ASTNode<'a>>),
+    CompoundDef(Box<ASTNode<'a>>, token::Operator), // VariableDef or ComplexDef, operator
     Boolean(bool),
     Number(f64),
     String(String),
+    Interpolation(Vec<(ASTNode<'a>, Location<'a>)>), // parts of an interpolated string, ie. `"x = {x}"`
     Expression(Expression<'a>),
     Tuple(AST<'a>, bool), // body, is_partial
     Block(AST<'a>),
     Interpretation(token::TypeName, token::TypeName, AST<'a>), // from, to, body
-    Struct(token::TypeName, AST<'a>),                          // name, body
+    Struct(StructDef<'a>),
+    Interface(InterfaceDef<'a>),
+    Enum(EnumDef<'a>),
+    StructLiteral(token::TypeName, AST<'a>),                   // name, fields
+    Extension(token::TypeName, AST<'a>),                       // extended primitive type, body
     Nil,
 }
 
 impl<'a> ASTNode<'a> {
+    pub fn compound(self, op: Option<token::Operator>) -> ASTNode<'a> {
+        //! Wraps a definition into a compound definition if it was made with an operator, ie. `x +: 1`
+        match op {
+            Some(op) => ASTNode::CompoundDef(Box::new(self), op),
+            None => self,
+        }
+    }
+
+    pub fn set_doc(&mut self, doc: String) -> bool {
+        //! Attaches a doc comment to a pattern, struct or variable declaration; returns false if the node cannot be documented
+        match self {
+            ASTNode::PatternDecl(Pattern { doc: d, .. })
+            | ASTNode::Struct(StructDef { doc: d, .. })
+            | ASTNode::Interface(InterfaceDef { doc: d, .. })
+            | ASTNode::Enum(EnumDef { doc: d, .. })
+            | ASTNode::VariableInit(_, _, d)
+            | ASTNode::ConstInit(_, _, d)
+            | ASTNode::StaticInit(_, _, d) => {
+                *d = Some(doc);
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn is_valid_expr_term(&self) -> bool {
         match self {
             ASTNode::Function(_)
             | ASTNode::Pattern(_)
             | ASTNode::PatternCall(_, _)
             | ASTNode::MethodCall(_, _)
+            | ASTNode::Match(_)
             | ASTNode::Variable(_)
             | ASTNode::Member(_)
             | ASTNode::Boolean(_)
             | ASTNode::Number(_)
             | ASTNode::String(_)
+            | ASTNode::Interpolation(_)
             | ASTNode::Tuple(_, _)
             | ASTNode::Block(_)
             | ASTNode::TypeName(_)
+            | ASTNode::StructLiteral(_, _)
             | ASTNode::Nil
             | ASTNode::VariableDef(_, _)
             | ASTNode::ComplexDef(_, _, _)
+            | ASTNode::CompoundDef(_, _)
             | ASTNode::VoidSymbol
             | ASTNode::Expression(_) => true,
             _ => false,
@@ -53,6 +93,9 @@ impl<'a> ASTNode<'a> {
     }
 
     pub fn is_valid_tuple_term(&self) -> bool {
+        if let ASTNode::NamedArg(_, _) = self {
+            return true;
+        }
         return self.is_valid_expr_term();
     }
 
@@ -60,7 +103,9 @@ impl<'a> ASTNode<'a> {
         match self {
             ASTNode::Variable(_)
             | ASTNode::TypedVariable(_, _)
+            | ASTNode::VariableDef(_, _)
             | ASTNode::PatternCall(_, _)
+            | ASTNode::Tuple(_, false)
             | ASTNode::VoidSymbol
             | ASTNode::Expression(_) => true,
             _ => false,
@@ -75,7 +120,9 @@ impl<'a> ASTNode<'a> {
             ASTNode::PatternDecl(_)
             | ASTNode::Interpretation(_, _, _)
             | ASTNode::VariableDecl(_)
-            | ASTNode::VariableInit(_, _) => true,
+            | ASTNode::VariableInit(_, _, _)
+            | ASTNode::ConstInit(_, _, _)
+            | ASTNode::DestructureInit(_, _) => true,
             _ => false,
         }
     }
@@ -85,16 +132,17 @@ impl<'a> ASTNode<'a> {
             return true;
         }
         match self {
-            ASTNode::Struct(_, _) => true,
+            ASTNode::Struct(_) | ASTNode::Interface(_) | ASTNode::Enum(_) | ASTNode::Extension(_, _) => true,
             _ => false,
         }
     }
 
     pub fn is_valid_struct_term(&self) -> bool {
         match self {
-            ASTNode::PatternDecl(_) | ASTNode::VariableDecl(_) | ASTNode::VariableInit(_, _) => {
-                true
-            }
+            ASTNode::PatternDecl(_)
+            | ASTNode::VariableDecl(_)
+            | ASTNode::VariableInit(_, _, _)
+            | ASTNode::StaticInit(_, _, _) => true,
             _ => false,
         }
     }
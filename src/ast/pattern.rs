@@ -2,6 +2,7 @@ use super::*;
 
 #[derive(Debug, Clone)]
 pub struct Pattern<'a> {
-    pub function: Function<'a>,
+    pub function: Box<Function<'a>>,
     pub name: String,
+    pub doc: Option<String>,
 }
@@ -0,0 +1,150 @@
+use super::*;
+use token::Operator;
+
+/// The resolved member assigned by a `ComplexDef`
+#[derive(Clone, Debug)]
+pub enum RDefineMember {
+    /// `expr.name: value`
+    Member(String),
+    /// `expr.0: value`, where `expr` is an assignable tuple (see `is_assignable`)
+    Index(usize),
+    /// `(a, b): value`
+    Tuple(RDestructure),
+}
+
+/// The resolved target of a compound definition, ie. `x +: 1`
+#[derive(Clone, Debug)]
+pub enum RDefineTarget<'a> {
+    Variable(RSymRef),
+    /// `expr.name +: value`
+    Member(RExpression<'a>, String),
+    /// `expr.0 +: value`, where `expr` is an assignable tuple
+    Index(RExpression<'a>, usize),
+}
+
+impl<'a> RDefineTarget<'a> {
+    pub fn from_def(def: RASTNode<'a>) -> (RDefineTarget<'a>, RASTNode<'a>) {
+        //! Splits a resolved `VariableDef` or `ComplexDef` into its target and its value
+        match def {
+            RASTNode::VariableDef(var, value) => (RDefineTarget::Variable(var), *value),
+            RASTNode::ComplexDef(expr, RDefineMember::Member(name), value) => {
+                (RDefineTarget::Member(expr, name), *value)
+            }
+            RASTNode::ComplexDef(expr, RDefineMember::Index(index), value) => {
+                (RDefineTarget::Index(expr, index), *value)
+            }
+            _ => unreachable!("Compound definitions are made of variable or member definitions"),
+        }
+    }
+}
+
+/// A resolved destructuring target
+#[derive(Clone, Debug)]
+pub enum RDestructure {
+    Ignore,
+    Variable(RSymRef),
+    Tuple(Vec<RDestructure>),
+}
+
+impl RDestructure {
+    pub fn resolve<'a>(target: Destructure<'a>, res: &RASTRef<'a>) -> RDestructure {
+        //! Resolves the variables of `target`, which must already have been declared
+        match target {
+            Destructure::Ignore => RDestructure::Ignore,
+            Destructure::Variable(name, loc) => RDestructure::Variable(lookup::lookup_variable(
+                name,
+                loc,
+                &res.borrow().variables,
+                res.clone(),
+            )),
+            Destructure::Tuple(elements, _) => RDestructure::Tuple(
+                elements
+                    .into_iter()
+                    .map(|element| RDestructure::resolve(element, res))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn variables(&self) -> Vec<&RSymRef> {
+        //! Returns the variables bound by the target, in order
+        match self {
+            RDestructure::Ignore => Vec::new(),
+            RDestructure::Variable(var) => vec![var],
+            RDestructure::Tuple(elements) => elements.iter().flat_map(|x| x.variables()).collect(),
+        }
+    }
+}
+
+pub fn resolve_define_member<'a>(
+    member: DefineMember<'a>,
+    expr: &RExpression<'a>,
+    res: &RASTRef<'a>,
+    loc: &Location<'a>,
+) -> RDefineMember {
+    //! Resolves the member of a `ComplexDef`, checking that tuple elements are only assigned within variables or fields
+    match member {
+        DefineMember::Member(name) => RDefineMember::Member(name),
+        DefineMember::Number(x) => {
+            if !is_assignable(&expr.terms) {
+                CompError::new(
+                    108,
+                    String::from("Cannot assign to an element of a temporary tuple: it must be stored in a variable or a field"),
+                    CompLocation::from(loc),
+                )
+                .print_and_exit();
+            }
+            RDefineMember::Index(x as usize)
+        }
+        DefineMember::Tuple(target) => RDefineMember::Tuple(RDestructure::resolve(target, res)),
+    }
+}
+
+pub fn check_define_member(member: &RDefineMember, expr: &RExpression, loc: &Location) {
+    //! Errors out if the variables assigned to by a `ComplexDef`, or the one holding the assigned field or element, are constants
+    if let RDefineMember::Tuple(target) = member {
+        for var in target.variables() {
+            check_mutable(var, loc);
+        }
+    }
+    if let Some(var) = assigned_variable(&expr.terms) {
+        check_mutable(var, loc);
+    }
+}
+
+fn assigned_variable<'b>(terms: &'b [RExprTerm]) -> Option<&'b RSymRef> {
+    //! Returns the variable in which the field or the element described by `terms` is stored, if any
+    match terms {
+        [RExprTerm::Push(RASTNode::Variable(var))] => Some(var),
+        [prefix @ .., RExprTerm::Push(_), RExprTerm::Op(Operator::MemberAccessor)] => {
+            assigned_variable(prefix)
+        }
+        _ => None,
+    }
+}
+
+pub fn is_assignable(terms: &[RExprTerm]) -> bool {
+    //! Returns wether or not `terms` is a variable, a field or an element of one of them, in which a new value can thus be stored
+    match terms {
+        [RExprTerm::Push(RASTNode::Variable(_))] => true,
+        [prefix @ .., RExprTerm::Push(RASTNode::Member(_)), RExprTerm::Op(Operator::MemberAccessor)] => {
+            !prefix.is_empty()
+        }
+        [prefix @ .., RExprTerm::Push(index), RExprTerm::Op(Operator::MemberAccessor)] => {
+            tuple_index(index).is_some() && is_assignable(prefix)
+        }
+        _ => false,
+    }
+}
+
+pub fn tuple_index(node: &RASTNode) -> Option<usize> {
+    //! Returns the index accessed by the right-hand side of a member accessor, which is wrapped in a tuple by the parser
+    match node {
+        RASTNode::Number(x) => Some(*x as usize),
+        RASTNode::Tuple(elements, false) => match elements.as_slice() {
+            [(RASTNode::Number(x), _)] => Some(*x as usize),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -1,16 +1,25 @@
 use super::*;
+use std::cell::OnceCell;
 use std::fmt;
 use token::TypeStrictness;
 
 #[derive(Clone)]
 pub struct RFunction<'a> {
+    pub name: Option<String>,
     pub args: Vec<RFunctionArg<'a>>,
+    pub rest: Option<String>,
     pub body: Rc<RefCell<RAST<'a>>>,
     pub has_self: bool,
     pub has_lhs: bool,
     pub has_new: bool,
+    pub has_super: bool,
     pub closure: Vec<(String, RASTRef<'a>)>,
     pub required_ctx: Option<(usize, u128, Location<'a>)>,
+    pub location: Location<'a>,
+    /// The doc comment of the pattern or variable declaration which defines the function
+    pub doc: Option<String>,
+    /// The function's bytecode, compiled by the VM on its first call
+    pub compiled: OnceCell<Rc<crate::vm::FunctionChunk<'a>>>,
 }
 
 impl<'a> From<(Function<'a>, RASTWeak<'a>, Location<'a>)> for RFunction<'a> {
@@ -33,6 +42,12 @@ impl<'a> From<(Function<'a>, RASTWeak<'a>, Location<'a>)> for RFunction<'a> {
                 .push(Rc::new(RefCell::new(RSymbol::new(arg.name.clone()))));
         }
 
+        if let Some(rest) = &function.rest {
+            init.borrow_mut()
+                .variables
+                .push(Rc::new(RefCell::new(RSymbol::new(rest.clone()))));
+        }
+
         for (name, value) in function.closure.into_iter() {
             init.borrow_mut()
                 .variables
@@ -50,11 +65,46 @@ impl<'a> From<(Function<'a>, RASTWeak<'a>, Location<'a>)> for RFunction<'a> {
                 .variables
                 .push(Rc::new(RefCell::new(RSymbol::new(String::from("self")))));
         }
+        if function.has_super {
+            init.borrow_mut()
+                .variables
+                .push(Rc::new(RefCell::new(RSymbol::new(String::from("super")))));
+        }
+
+        for arg in function.args.iter() {
+            for name in arg.pattern.iter().flat_map(|pattern| pattern.names()) {
+                init.borrow_mut()
+                    .variables
+                    .push(Rc::new(RefCell::new(RSymbol::new(name))));
+            }
+        }
+
+        let args: Vec<RFunctionArg<'a>> = function
+            .args
+            .into_iter()
+            .map(|mut arg| {
+                let default = arg.default.take();
+                let pattern = arg.pattern.take();
+                let mut rarg = RFunctionArg::from((arg, parent.clone(), loc.clone()));
+                // default values are evaluated within the function's context
+                rarg.default = default.map(|value| RAST::resolve(value, Rc::downgrade(&init)));
+                rarg.pattern = pattern.map(|pattern| RDestructure::resolve(pattern, &init));
+                rarg
+            })
+            .collect();
 
         let body = RAST::resolve(function.body, Rc::downgrade(&init));
 
         let mut required_ctx =
             scan_body_reqs(body.clone(), &function.refs, init.borrow().depth, &loc);
+        for arg in args.iter() {
+            if let Some(default) = &arg.default {
+                required_ctx = merge_reqs(
+                    required_ctx,
+                    scan_body_reqs(default.clone(), &function.refs, init.borrow().depth, &loc),
+                );
+            }
+        }
         if let Some((depth, _, _)) = required_ctx {
             if depth >= init.borrow().depth {
                 required_ctx = None;
@@ -66,26 +116,73 @@ impl<'a> From<(Function<'a>, RASTWeak<'a>, Location<'a>)> for RFunction<'a> {
             .push((RASTNode::Block(body), loc.clone()));
 
         RFunction {
-            args: function
-                .args
-                .into_iter()
-                .map(|arg| RFunctionArg::from((arg, parent.clone(), loc.clone())))
-                .collect(),
+            name: None,
+            args,
+            rest: function.rest,
             body: init,
             has_lhs: function.has_lhs,
             has_self: function.has_self,
             has_new: function.has_new,
+            has_super: function.has_super,
             closure,
             required_ctx,
+            location: loc,
+            doc: None,
+            compiled: OnceCell::new(),
         }
     }
 }
 
+/** The slots of the variables that a call binds within the function's `init` context.
+    Its arguments come first, followed by its #rest() argument, its closure, `lhs`, `self`, `super` and the variables of its destructured arguments, in the order in which `RFunction::from` declares them.
+**/
+impl<'a> RFunction<'a> {
+    pub fn required_args_n(&self) -> usize {
+        self.args.iter().filter(|arg| arg.default.is_none()).count()
+    }
+
+    pub fn accepts_args_n(&self, n: usize) -> bool {
+        //! Returns wether or not the function may be called with `n` arguments, be they positional or named
+        n >= self.required_args_n() && (self.rest.is_some() || n <= self.args.len())
+    }
+
+    pub fn rest_slot(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn closure_slot(&self, index: usize) -> usize {
+        self.args.len() + self.rest.iter().count() + index
+    }
+
+    pub fn lhs_slot(&self) -> usize {
+        self.closure_slot(self.closure.len())
+    }
+
+    pub fn self_slot(&self) -> usize {
+        self.lhs_slot() + if self.has_lhs { 1 } else { 0 }
+    }
+
+    pub fn super_slot(&self) -> usize {
+        self.self_slot() + if self.has_self || self.has_new { 1 } else { 0 }
+    }
+
+    pub fn takes_self(&self) -> bool {
+        //! Returns wether or not the function is a method taking `#self()` (or `#super()`), which must thus be called on an instance
+        self.has_self || self.has_super
+    }
+}
+
 // NOTE: this assumes that the block is the last instruction of the `init` RAST
 impl<'a> fmt::Debug for RFunction<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut builder = f.debug_struct("RFunction");
+        if let Some(name) = &self.name {
+            builder.field("name", name);
+        }
         builder.field("args", &self.args);
+        if let Some(rest) = &self.rest {
+            builder.field("rest", rest);
+        }
         let instructions = &self.body.borrow().instructions;
         match &instructions[instructions.len() - 1].0 {
             RASTNode::Block(expr) => {
@@ -106,10 +203,16 @@ pub struct RFunctionArg<'a> {
     pub name: String,
     pub argtype: RStructWeak<'a>,
     pub strictness: TypeStrictness,
+    pub default: Option<RASTRef<'a>>,
+    /// The variables into which the argument is destructured, ie. `(a, b)` in `((a, b)) => {}`
+    pub pattern: Option<RDestructure>,
 }
 
-impl<'a> From<(FunctionArg, RASTWeak<'a>, Location<'a>)> for RFunctionArg<'a> {
-    fn from(input: (FunctionArg, RASTWeak<'a>, Location<'a>)) -> RFunctionArg<'a> {
+impl<'a> From<(FunctionArg<'a>, RASTWeak<'a>, Location<'a>)> for RFunctionArg<'a> {
+    /**
+      Creates an RFunctionArg off a FunctionArg; its default value and its pattern are left for RFunction::from to resolve.
+    */
+    fn from(input: (FunctionArg<'a>, RASTWeak<'a>, Location<'a>)) -> RFunctionArg<'a> {
         let name = input.0.name;
         let parent = input.1;
         let loc = input.2;
@@ -126,12 +229,16 @@ impl<'a> From<(FunctionArg, RASTWeak<'a>, Location<'a>)> for RFunctionArg<'a> {
                 name,
                 argtype: Rc::downgrade(&st),
                 strictness: argtype.strictness,
+                default: None,
+                pattern: None,
             }
         } else {
             RFunctionArg {
                 name,
                 argtype: Weak::new(),
                 strictness: TypeStrictness::Normal,
+                default: None,
+                pattern: None,
             }
         }
     }
@@ -172,14 +279,46 @@ fn scan_body_reqs_node<'a>(
             scan_body_reqs_node((value.as_ref(), loc), refs, max_depth, fn_location),
             scan_body_reqs_sym(sym, loc, refs, max_depth, fn_location),
         ),
+        (RASTNode::NamedArg(_, value), loc) => {
+            scan_body_reqs_node((value.as_ref(), loc), refs, max_depth, fn_location)
+        }
         (RASTNode::PatternCall(_, rast), _loc)
         | (RASTNode::Block(rast), _loc)
-        | (RASTNode::MethodCall(_, rast), _loc) => {
+        | (RASTNode::MethodCall(_, rast, _), _loc) => {
             scan_body_reqs(rast.clone(), refs, max_depth, fn_location)
         }
-        (RASTNode::ComplexDef(expr, _, value), loc) => merge_reqs(
+        (RASTNode::Match(value, arms), _loc) => {
+            let mut res = scan_body_reqs(value.clone(), refs, max_depth, fn_location);
+            for arm in arms.iter() {
+                if let Some(guard) = &arm.guard {
+                    res = merge_reqs(res, scan_body_reqs(guard.clone(), refs, max_depth, fn_location));
+                }
+                res = merge_reqs(res, scan_body_reqs(arm.body.clone(), refs, max_depth, fn_location));
+            }
+            res
+        }
+        (RASTNode::ComplexDef(expr, member, value), loc) => {
+            let mut res = merge_reqs(
+                scan_body_reqs_node((value.as_ref(), loc), refs, max_depth, fn_location),
+                scan_body_reqs_expr(expr, loc, refs, max_depth, fn_location),
+            );
+            if let RDefineMember::Tuple(target) = member {
+                for sym in target.variables() {
+                    res = merge_reqs(res, scan_body_reqs_sym(sym, loc, refs, max_depth, fn_location));
+                }
+            }
+            res
+        }
+        (RASTNode::CompoundDef(target, _, value), loc) => merge_reqs(
             scan_body_reqs_node((value.as_ref(), loc), refs, max_depth, fn_location),
-            scan_body_reqs_expr(expr, loc, refs, max_depth, fn_location),
+            match target {
+                RDefineTarget::Variable(sym) => {
+                    scan_body_reqs_sym(sym, loc, refs, max_depth, fn_location)
+                }
+                RDefineTarget::Member(expr, _) | RDefineTarget::Index(expr, _) => {
+                    scan_body_reqs_expr(expr, loc, refs, max_depth, fn_location)
+                }
+            },
         ),
         (RASTNode::Expression(expr), loc) => {
             scan_body_reqs_expr(expr, loc, refs, max_depth, fn_location)
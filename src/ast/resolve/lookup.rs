@@ -8,15 +8,16 @@ use super::*;
 pub fn lookup_variable<'a, 'b>(
     name: String,
     loc: Location<'b>,
-    variables: &'b Vec<Rc<RefCell<RSymbol>>>,
+    variables: &'b [Rc<RefCell<RSymbol>>],
     current: RASTRef<'a>,
 ) -> RSymRef {
-    for var in variables {
+    for (slot, var) in variables.iter().enumerate() {
         if var.borrow().name == name {
             return RSymRef::new(
                 var.clone(),
                 current.borrow().depth,
                 current.borrow().ulid,
+                slot,
             );
         }
     }
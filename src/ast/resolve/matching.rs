@@ -0,0 +1,131 @@
+use super::*;
+use crate::interpreter::VariableValue;
+use std::cell::OnceCell;
+
+/** A resolved arm of a `#match`.
+    The names bound by its pattern are the variables of `scope`, within which its guard and body are resolved; they are thus only visible from there.
+**/
+#[derive(Debug, Clone)]
+pub struct RMatchArm<'a> {
+    pub pattern: RMatchPattern<'a>,
+    pub scope: RASTRef<'a>,
+    pub guard: Option<RASTRef<'a>>,
+    pub body: RASTRef<'a>,
+    pub location: Location<'a>,
+    /// The arm's bytecode, compiled by the VM on its first use
+    pub compiled: OnceCell<Rc<crate::vm::MatchArmChunk<'a>>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum RMatchPattern<'a> {
+    Wildcard,
+    Literal(VariableValue<'a>),
+    /// Binds the value to the variable at this slot of the arm's scope
+    Bind(usize),
+    Tuple(Vec<RMatchPattern<'a>>),
+    Struct(RStructRef<'a>, Vec<(String, RMatchPattern<'a>)>),
+}
+
+/// Resolves the matched value and the arms of a `#match`, within `res`
+pub fn resolve_match<'a>(m: Match<'a>, res: &RASTRef<'a>) -> RASTNode<'a> {
+    let value = RAST::resolve(m.value, Rc::downgrade(res));
+    let arms = m
+        .arms
+        .into_iter()
+        .map(|arm| {
+            let scope = Rc::new(RefCell::new(RAST::new(Rc::downgrade(res), ASTKind::Block)));
+            let pattern = resolve_pattern(arm.pattern, &scope, res, &arm.location);
+            let guard = arm
+                .guard
+                .map(|guard| RAST::resolve(guard, Rc::downgrade(&scope)));
+            let body = RAST::resolve(arm.body, Rc::downgrade(&scope));
+            RMatchArm {
+                pattern,
+                scope,
+                guard,
+                body,
+                location: arm.location,
+                compiled: OnceCell::new(),
+            }
+        })
+        .collect();
+
+    RASTNode::Match(value, Rc::new(arms))
+}
+
+fn resolve_pattern<'a>(
+    pattern: MatchPattern<'a>,
+    scope: &RASTRef<'a>,
+    res: &RASTRef<'a>,
+    location: &Location<'a>,
+) -> RMatchPattern<'a> {
+    //! Resolves a pattern, declaring the names that it binds in `scope`
+    match pattern {
+        MatchPattern::Wildcard => RMatchPattern::Wildcard,
+        MatchPattern::Literal(ASTNode::Number(x)) => RMatchPattern::Literal(VariableValue::Number(x)),
+        MatchPattern::Literal(ASTNode::String(x)) => RMatchPattern::Literal(VariableValue::String(x)),
+        MatchPattern::Literal(ASTNode::Boolean(x)) => RMatchPattern::Literal(VariableValue::Boolean(x)),
+        MatchPattern::Literal(_) => unreachable!("Literal patterns are numbers, strings or booleans"),
+        MatchPattern::Bind(name) => {
+            if scope.borrow().variables.iter().any(|var| var.borrow().name == name) {
+                CompError::new(
+                    161,
+                    format!("Invalid match pattern: {} is bound more than once", name),
+                    CompLocation::from(location),
+                )
+                .print_and_exit();
+            }
+            scope
+                .borrow_mut()
+                .variables
+                .push(Rc::new(RefCell::new(RSymbol::new(name))));
+            RMatchPattern::Bind(scope.borrow().variables.len() - 1)
+        }
+        MatchPattern::Tuple(elements) => RMatchPattern::Tuple(
+            elements
+                .into_iter()
+                .map(|element| resolve_pattern(element, scope, res, location))
+                .collect(),
+        ),
+        MatchPattern::Struct(name, fields, loc) => {
+            let parent = res.borrow().parent.clone();
+            let st = lookup::lookup_struct(name, loc.clone(), &res.borrow().structs, parent);
+            let fields = match fields {
+                StructPatternFields::Any => Vec::new(),
+                StructPatternFields::Positional(elements) => {
+                    let names = st.borrow().fields();
+                    if st.borrow().tag.is_none() || names.len() != elements.len() {
+                        CompError::new(
+                            161,
+                            format!(
+                                "Invalid match pattern: {} takes {} positional field(s), but {} were given",
+                                st.borrow().name,
+                                if st.borrow().tag.is_some() { names.len() } else { 0 },
+                                elements.len()
+                            ),
+                            CompLocation::from(&loc),
+                        )
+                        .print_and_exit();
+                    }
+                    names.into_iter().zip(elements).collect()
+                }
+                StructPatternFields::Named(fields) => fields,
+            };
+            let fields = fields
+                .into_iter()
+                .map(|(field, pattern)| {
+                    if !st.borrow().has_field(&field) {
+                        CompError::new(
+                            161,
+                            format!("Invalid match pattern: unknown field {} in struct {}", field, st.borrow().name),
+                            CompLocation::from(&loc),
+                        )
+                        .print_and_exit();
+                    }
+                    (field, resolve_pattern(pattern, scope, res, location))
+                })
+                .collect();
+            RMatchPattern::Struct(st, fields)
+        }
+    }
+}
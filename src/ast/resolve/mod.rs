@@ -1,17 +1,23 @@
+pub mod define;
 pub mod expr;
 pub mod function;
 pub mod lookup;
+pub mod matching;
 pub mod node;
 pub mod pattern;
+pub mod primitive;
 pub mod r#struct;
 pub mod variable;
 
 pub use super::*;
 pub use crate::interpreter::Callable;
+pub use define::*;
 pub use expr::*;
 pub use function::*;
+pub use matching::*;
 pub use node::*;
 pub use pattern::*;
+pub use primitive::*;
 pub use r#struct::*;
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -42,6 +48,7 @@ pub struct RAST<'a> {
     pub depth: usize,
     pub kind: ASTKind,
     pub ulid: u128,
+    pub primitive_methods: Option<PrimitiveMethodsRef<'a>>, // only set on the standard RAST, see `RAST::primitive_methods`
     declared_patterns: Vec<Rc<RPattern<'a>>>, // helper Vec, used by RAST::resolve and RAST::resolve_node
 }
 
@@ -70,6 +77,19 @@ impl<'a> RAST<'a> {
             kind,
             declared_patterns: Vec::new(),
             ulid: Ulid::generate().into(),
+            primitive_methods: None,
+        }
+    }
+
+    pub fn primitive_methods(res: &RASTRef<'a>) -> PrimitiveMethodsRef<'a> {
+        //! Returns the methods of the primitive types, held by the outermost RAST
+        if let Some(methods) = &res.borrow().primitive_methods {
+            return methods.clone();
+        }
+        let parent = res.borrow().parent.upgrade();
+        match parent {
+            Some(parent) => RAST::primitive_methods(&parent),
+            None => panic!("Expected the standard RAST to hold the methods of the primitive types"),
         }
     }
 
@@ -85,19 +105,50 @@ impl<'a> RAST<'a> {
         for instruction in ast.instructions.iter() {
             // first pass: find variables and patterns
             match &instruction.0 {
-                ASTNode::VariableDecl(name) | ASTNode::VariableInit(name, _) => res
+                ASTNode::VariableDecl(name) | ASTNode::VariableInit(name, _, _) => res
                     .borrow_mut()
                     .variables
                     .push(Rc::new(RefCell::new(RSymbol::new(name.clone())))),
+                ASTNode::StaticInit(name, _, _) => res
+                    .borrow_mut()
+                    .variables
+                    .push(Rc::new(RefCell::new(RSymbol::new_static(name.clone())))),
+                ASTNode::ConstInit(name, _, _) => res
+                    .borrow_mut()
+                    .variables
+                    .push(Rc::new(RefCell::new(RSymbol::new_constant(
+                        name.clone(),
+                        &instruction.1,
+                    )))),
+                ASTNode::DestructureInit(target, _) => {
+                    for name in target.names() {
+                        res.borrow_mut()
+                            .variables
+                            .push(Rc::new(RefCell::new(RSymbol::new(name))));
+                    }
+                }
                 ASTNode::PatternDecl(p) => {
-                    let pat = Rc::new(RPattern::new(p.name.clone()));
-                    res.borrow_mut().patterns.push(pat.clone());
+                    // declaring a pattern again within the same scope overloads it
+                    let declared = res.borrow().declared_patterns.iter().find(|pat| pat.name == p.name).cloned();
+                    let pat = declared.unwrap_or_else(|| {
+                        let pat = Rc::new(RPattern::new(p.name.clone()));
+                        res.borrow_mut().patterns.push(pat.clone());
+                        pat
+                    });
                     res.borrow_mut().declared_patterns.push(pat);
                 }
-                ASTNode::Struct(name, _) => res
-                    .borrow_mut()
-                    .structs
-                    .push(Rc::new(RefCell::new(RStruct::new(name.clone())))),
+                ASTNode::Struct(def) => {
+                    let mut st = RStruct::new(def.name.clone());
+                    st.doc = def.doc.clone();
+                    declare_type(&res, Rc::new(RefCell::new(st)), &instruction.1);
+                }
+                ASTNode::Enum(def) => resolve_enum(def, &res, &instruction.1),
+                ASTNode::Interface(def) => {
+                    let mut st = RStruct::new(def.name.clone());
+                    st.doc = def.doc.clone();
+                    st.interface = Some(def.members.clone());
+                    declare_type(&res, Rc::new(RefCell::new(st)), &instruction.1);
+                }
                 _ => {}
             }
         }
@@ -111,6 +162,18 @@ impl<'a> RAST<'a> {
             .collect();
         res.borrow_mut().declared_patterns = rev_declared_patterns;
 
+        // interfaces are checked once every struct of the scope is resolved, as members may be inherited from a struct declared later on
+        let implementations: Vec<_> = ast
+            .instructions
+            .iter()
+            .filter_map(|(node, loc)| match node {
+                ASTNode::Struct(def) if !def.interfaces.is_empty() => {
+                    Some((def.name.clone(), loc.clone(), def.interfaces.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+
         for instruction in ast.instructions.into_iter() {
             // second pass: resolve instructions
             let loc = instruction.1.clone();
@@ -123,9 +186,38 @@ impl<'a> RAST<'a> {
             }
         }
 
+        for (name, loc, interfaces) in implementations {
+            let st = lookup::lookup_struct(name, loc, &res.borrow().structs, parent.clone());
+            for (interface_name, interface_loc) in interfaces {
+                let interface = lookup::lookup_struct(
+                    interface_name,
+                    interface_loc.clone(),
+                    &res.borrow().structs,
+                    parent.clone(),
+                );
+                check_interface(&st, &interface, &interface_loc);
+                st.borrow_mut().interfaces.push(interface);
+            }
+        }
+
         res
     }
 
+    fn resolve_definition(
+        s: RSymRef,
+        expr: ASTNode<'a>,
+        loc: Location<'a>,
+        res: RASTRef<'a>,
+    ) -> RASTNode<'a> {
+        //! Resolves the value bound to `s` by a variable or constant definition
+        let value = RAST::resolve_node((expr, loc), res).unwrap_or(RASTNode::Nil);
+        if let RASTNode::Function(rfn) = &value {
+            // functions bound to a variable take its name
+            rfn.borrow_mut().name = Some(s.name.clone());
+        }
+        RASTNode::VariableDef(s, Box::new(value))
+    }
+
     /** Resolves an individual node and optionally returns an instruction
      */
     pub fn resolve_node(
@@ -134,21 +226,44 @@ impl<'a> RAST<'a> {
     ) -> Option<RASTNode<'a>> {
         let loc = node.1;
         let parent = res.borrow().parent.clone();
+        let is_const = matches!(node.0, ASTNode::ConstInit(_, _, _));
+        let is_static = matches!(node.0, ASTNode::StaticInit(_, _, _));
         match node.0 {
-            ASTNode::VariableInit(name, expr) | ASTNode::VariableDef(name, expr) => {
+            ASTNode::VariableInit(name, expr, doc)
+            | ASTNode::ConstInit(name, expr, doc)
+            | ASTNode::StaticInit(name, expr, doc) => {
                 let s = lookup::lookup_variable(
                     name,
                     loc.clone(),
                     &res.borrow().variables,
                     res.clone(),
                 );
-                Some(RASTNode::VariableDef(
-                    s,
-                    Box::new(
-                        RAST::resolve_node((*expr, loc.clone()), res.clone())
-                            .unwrap_or(RASTNode::Nil),
-                    ),
-                ))
+                // a constant is the only declaration of its name within its scope
+                if is_const {
+                    check_redeclared_constant(&s, &loc);
+                } else {
+                    check_mutable(&s, &loc);
+                }
+                if matches!(res.borrow().kind, ASTKind::Struct) {
+                    check_member_kind(&s, is_static, &loc);
+                }
+                let def = RAST::resolve_definition(s, *expr, loc, res);
+                if let RASTNode::VariableDef(_, value) = &def {
+                    if let RASTNode::Function(rfn) = value.as_ref() {
+                        rfn.borrow_mut().doc = doc;
+                    }
+                }
+                Some(def)
+            }
+            ASTNode::VariableDef(name, expr) => {
+                let s = lookup::lookup_variable(
+                    name,
+                    loc.clone(),
+                    &res.borrow().variables,
+                    res.clone(),
+                );
+                check_mutable(&s, &loc);
+                Some(RAST::resolve_definition(s, *expr, loc, res))
             }
             ASTNode::Interpretation(from, to, body) => {
                 let from =
@@ -164,9 +279,14 @@ impl<'a> RAST<'a> {
                 None
             }
             ASTNode::PatternDecl(p) => {
+                if p.function.has_super && !matches!(res.borrow().kind, ASTKind::Struct) {
+                    err_misplaced_super(&loc, String::from("the pattern isn't declared within a struct"));
+                }
                 let pat = res.borrow_mut().declared_patterns.pop().unwrap();
-                let function = RFunction::from((p.function, Rc::downgrade(&res), loc));
-                pat.set_function(function);
+                let mut function = RFunction::from((*p.function, Rc::downgrade(&res), loc));
+                function.name = Some(p.name);
+                function.doc = p.doc;
+                pat.add_overload(function);
                 None
             }
             ASTNode::PatternCall(name, args) => {
@@ -177,20 +297,59 @@ impl<'a> RAST<'a> {
                     parent.clone(),
                 );
                 let args = RAST::resolve(args, Rc::downgrade(&res));
+                pattern::check_named_args(&pat, &args);
                 Some(RASTNode::PatternCall(pat, args))
             }
+            ASTNode::Match(m) => Some(resolve_match(*m, &res)),
             ASTNode::MethodCall(name, args) => {
                 let args = RAST::resolve(args, Rc::downgrade(&res));
-                Some(RASTNode::MethodCall(name, args))
+                Some(RASTNode::MethodCall(name, args, RAST::primitive_methods(&res)))
+            }
+            ASTNode::Extension(type_name, body) => {
+                resolve_extension(&type_name, body, &loc, &res);
+                None
             }
-            ASTNode::Struct(name, body) => {
+            ASTNode::Struct(StructDef {
+                name,
+                parent: super_name,
+                body,
+                ..
+            }) => {
                 let st =
                     lookup::lookup_struct(name, loc.clone(), &res.borrow().structs, parent.clone());
+                if let Some(super_name) = super_name {
+                    let super_st = lookup::lookup_struct(
+                        super_name,
+                        loc.clone(),
+                        &res.borrow().structs,
+                        parent.clone(),
+                    );
+                    if super_st.borrow().is_descendant_of(&st) {
+                        CompError::new(
+                            157,
+                            format!("Cyclic inheritance: {} cannot extend one of its descendants", st.borrow().name),
+                            CompLocation::from(loc),
+                        )
+                        .print_and_exit();
+                    }
+                    st.borrow_mut().parent = Some(super_st);
+                }
+
+                if st.borrow().parent.is_none() {
+                    for (node, loc) in body.instructions.iter() {
+                        if let ASTNode::PatternDecl(p) = node {
+                            if p.function.has_super {
+                                err_misplaced_super(loc, format!("{} doesn't extend another struct", st.borrow().name));
+                            }
+                        }
+                    }
+                }
                 st.borrow_mut().context = Some(RAST::resolve(body, Rc::downgrade(&res)));
                 None
             }
+            ASTNode::Interface(_) | ASTNode::Enum(_) => None,
             ASTNode::Function(function) => {
-                let rfn = RFunction::from((function, Rc::downgrade(&res), loc));
+                let rfn = RFunction::from((*function, Rc::downgrade(&res), loc));
                 Some(RASTNode::Function(Rc::new(RefCell::new(rfn))))
             }
             ASTNode::Pattern(name) => {
@@ -211,10 +370,23 @@ impl<'a> RAST<'a> {
                 );
                 Some(RASTNode::Variable(var))
             }
+            ASTNode::NamedArg(name, value) => Some(RASTNode::NamedArg(
+                name,
+                Box::new(RAST::resolve_node((*value, loc), res.clone()).unwrap_or(RASTNode::Nil)),
+            )),
             ASTNode::Member(name) => Some(RASTNode::Member(name)),
             ASTNode::Boolean(b) => Some(RASTNode::Boolean(b)),
             ASTNode::Number(num) => Some(RASTNode::Number(num)),
             ASTNode::String(string) => Some(RASTNode::String(string)),
+            ASTNode::Interpolation(parts) => Some(RASTNode::Interpolation(
+                parts
+                    .into_iter()
+                    .map(|(part, loc)| {
+                        let node = RAST::resolve_node((part, loc.clone()), res.clone()).unwrap_or(RASTNode::Nil);
+                        (node, loc)
+                    })
+                    .collect(),
+            )),
             ASTNode::Expression(expr) => {
                 let mut terms: Vec<RExprTerm<'a>> = Vec::with_capacity(expr.terms.len());
                 let mut depth: usize = 0;
@@ -241,17 +413,38 @@ impl<'a> RAST<'a> {
                 }
                 Some(RASTNode::Expression(RExpression { terms, max_depth }))
             }
+            ASTNode::DestructureInit(target, val) => {
+                let target = RDestructure::resolve(target, &res);
+                let val = RAST::resolve_node((*val, loc), res.clone()).unwrap_or(RASTNode::Nil);
+                Some(RASTNode::ComplexDef(
+                    RExpression {
+                        terms: Vec::new(),
+                        max_depth: 0,
+                    },
+                    RDefineMember::Tuple(target),
+                    Box::new(val),
+                ))
+            }
             ASTNode::ComplexDef(expr, member, val) => {
                 let expr =
                     RAST::resolve_node((ASTNode::Expression(expr), loc.clone()), res.clone())
                         .unwrap();
-                let val = RAST::resolve_node((*val, loc), res.clone()).unwrap_or(RASTNode::Nil);
+                let val =
+                    RAST::resolve_node((*val, loc.clone()), res.clone()).unwrap_or(RASTNode::Nil);
                 if let RASTNode::Expression(expr) = expr {
+                    let member = resolve_define_member(member, &expr, &res, &loc);
+                    check_define_member(&member, &expr, &loc);
                     Some(RASTNode::ComplexDef(expr, member, Box::new(val)))
                 } else {
                     panic!("RAST::resolve_node did not return an expression");
                 }
             }
+            ASTNode::CompoundDef(def, op) => {
+                let def = RAST::resolve_node((*def, loc), res)
+                    .expect("Definitions always resolve to an instruction");
+                let (target, value) = RDefineTarget::from_def(def);
+                Some(RASTNode::CompoundDef(target, op, Box::new(value)))
+            }
             ASTNode::Tuple(ast, is_partial) => {
                 let mut elements: Vec<(RASTNode<'a>, Location<'a>)> =
                     Vec::with_capacity(ast.instructions.len());
@@ -271,11 +464,99 @@ impl<'a> RAST<'a> {
             ASTNode::TypeName(name) => {
                 let st =
                     lookup::lookup_struct(name, loc.clone(), &res.borrow().structs, parent.clone());
+                // variants without payload are values on their own, like `Idle`
+                if st.borrow().tag.is_some() && st.borrow().fields().is_empty() {
+                    return Some(RASTNode::StructLiteral(st, Vec::new()));
+                }
                 Some(RASTNode::TypeName(st))
             }
+            ASTNode::StructLiteral(name, fields) => {
+                let st =
+                    lookup::lookup_struct(name, loc.clone(), &res.borrow().structs, parent.clone());
+                if st.borrow().interface.is_some() {
+                    CompError::new(
+                        159,
+                        format!("Cannot instantiate {}: it is an interface", st.borrow().name),
+                        CompLocation::from(loc),
+                    )
+                    .print_and_exit();
+                }
+                if let Some(variants) = &st.borrow().variants {
+                    let variants: Vec<String> = variants
+                        .iter()
+                        .filter_map(|v| v.upgrade().map(|v| v.borrow().name.to_string()))
+                        .collect();
+                    CompError::new(
+                        160,
+                        format!(
+                            "Cannot instantiate {}: it is an enum, use one of its variants ({}) instead",
+                            st.borrow().name,
+                            variants.join(", ")
+                        ),
+                        CompLocation::from(loc),
+                    )
+                    .print_and_exit();
+                }
+
+                // the fields of a variant may be given in order, as in `Rect(2, 3)`
+                let mut names = st.borrow().fields().into_iter();
+                let positional = fields
+                    .instructions
+                    .iter()
+                    .filter(|(node, _)| !matches!(node, ASTNode::NamedArg(_, _)))
+                    .count();
+                if positional > 0 && st.borrow().tag.is_none() {
+                    CompError::new(
+                        160,
+                        format!("Invalid struct literal: {} is not an enum variant, so its fields must be named", st.borrow().name),
+                        CompLocation::from(loc),
+                    )
+                    .print_and_exit();
+                } else if positional > 0 && positional != names.len() {
+                    CompError::new(
+                        160,
+                        format!(
+                            "Mismatching number of fields for variant {}: expected {}, got {}",
+                            st.borrow().name,
+                            names.len(),
+                            positional
+                        ),
+                        CompLocation::from(loc),
+                    )
+                    .print_and_exit();
+                }
+
+                let fields = fields
+                    .instructions
+                    .into_iter()
+                    .map(|(node, loc)| match node {
+                        ASTNode::NamedArg(name, value) => {
+                            let value = RAST::resolve_node((*value, loc.clone()), res.clone())
+                                .unwrap_or(RASTNode::Nil);
+                            (name, value, loc)
+                        }
+                        node => {
+                            let value = RAST::resolve_node((node, loc.clone()), res.clone())
+                                .unwrap_or(RASTNode::Nil);
+                            (names.next().unwrap(), value, loc)
+                        }
+                    })
+                    .collect();
+                Some(RASTNode::StructLiteral(st, fields))
+            }
             ASTNode::VoidSymbol => Some(RASTNode::VoidSymbol),
             ASTNode::Nil => Some(RASTNode::Nil),
             _ => None,
         }
     }
 }
+
+fn err_misplaced_super(loc: &Location, reason: String) -> ! {
+    CompError::new(
+        157,
+        String::from("#super() can only be used within the methods of a struct extending another one"),
+        CompLocation::from(loc),
+    )
+    .append(reason, CompLocation::None)
+    .print_and_exit()
+}
@@ -5,10 +5,13 @@ use std::fmt;
 pub enum RASTNode<'a> {
     // resolved AST node
     PatternCall(RPatRef<'a>, RASTRef<'a>),
-    MethodCall(String, RASTRef<'a>),
+    MethodCall(String, RASTRef<'a>, PrimitiveMethodsRef<'a>), // name, args, methods of the primitive types
+    Match(RASTRef<'a>, Rc<Vec<RMatchArm<'a>>>), // value, arms
     Member(String),
     VariableDef(RSymRef, Box<RASTNode<'a>>),
-    ComplexDef(RExpression<'a>, DefineMember<'a>, Box<RASTNode<'a>>),
+    NamedArg(String, Box<RASTNode<'a>>),
+    ComplexDef(RExpression<'a>, RDefineMember, Box<RASTNode<'a>>),
+    CompoundDef(RDefineTarget<'a>, token::Operator, Box<RASTNode<'a>>), // target, operator, value
     Function(RFunRef<'a>),
     Pattern(RPatRef<'a>),
     Variable(RSymRef),
@@ -18,7 +21,9 @@ pub enum RASTNode<'a> {
     Boolean(bool),
     Number(f64),
     String(String),
+    Interpolation(Vec<(RASTNode<'a>, Location<'a>)>),
     TypeName(RStructRef<'a>),
+    StructLiteral(RStructRef<'a>, Vec<(String, RASTNode<'a>, Location<'a>)>), // type, fields
     Nil,
     VoidSymbol,
 }
@@ -31,22 +36,38 @@ impl<'a> fmt::Debug for RASTNode<'a> {
                 .field(&pat.get_name())
                 .field(&args.borrow())
                 .finish(),
-            RASTNode::MethodCall(name, args) => f
+            RASTNode::MethodCall(name, args, _) => f
                 .debug_tuple("MethodCall")
                 .field(&name)
                 .field(&args.borrow())
                 .finish(),
+            RASTNode::Match(value, arms) => f
+                .debug_tuple("Match")
+                .field(&value.borrow())
+                .field(&arms)
+                .finish(),
             RASTNode::VariableDef(var, value) => f
                 .debug_tuple("VariableDef")
                 .field(&var.name)
                 .field(&value)
                 .finish(),
+            RASTNode::NamedArg(name, value) => f
+                .debug_tuple("NamedArg")
+                .field(&name)
+                .field(&value)
+                .finish(),
             RASTNode::ComplexDef(expr, member, val) => f
                 .debug_tuple("ComplexDef")
                 .field(&expr)
                 .field(&member)
                 .field(val)
                 .finish(),
+            RASTNode::CompoundDef(target, op, val) => f
+                .debug_tuple("CompoundDef")
+                .field(&target)
+                .field(&op)
+                .field(val)
+                .finish(),
             RASTNode::Function(rfn) => f.debug_tuple("Function").field(&rfn.borrow()).finish(),
             RASTNode::Pattern(rfn) => f.debug_tuple("Pattern").field(&rfn.get_name()).finish(),
             RASTNode::Variable(var) => f.debug_tuple("Variable").field(&var.name).finish(),
@@ -55,9 +76,15 @@ impl<'a> fmt::Debug for RASTNode<'a> {
             RASTNode::Block(rast) => f.debug_tuple("Block").field(&rast).finish(),
             RASTNode::Tuple(vec, is_partial) => f.debug_tuple(if *is_partial {"PartialTuple"} else {"Tuple"}).field(&vec).finish(),
             RASTNode::TypeName(rstruct) => write!(f, "{:?}", rstruct.borrow().name),
+            RASTNode::StructLiteral(rstruct, fields) => f
+                .debug_tuple("StructLiteral")
+                .field(&rstruct.borrow().name)
+                .field(&fields.iter().map(|(name, value, _)| (name, value)).collect::<Vec<_>>())
+                .finish(),
             RASTNode::Boolean(b) => write!(f, "Boolean({})", b),
             RASTNode::Number(x) => write!(f, "Number({})", x),
             RASTNode::String(string) => write!(f, "String({})", string),
+            RASTNode::Interpolation(parts) => f.debug_tuple("Interpolation").field(&parts).finish(),
             RASTNode::Nil => write!(f, "Nil"),
             RASTNode::VoidSymbol => write!(f, "VoidSymbol"),
         }
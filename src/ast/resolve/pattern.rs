@@ -3,18 +3,100 @@ use super::*;
 #[derive(Debug, Clone)]
 pub struct RPattern<'a> {
     pub name: String,
-    pub function: RefCell<Option<RFunction<'a>>>,
+    /// The declarations of the pattern within its scope, which differ by their number of arguments or by their types; see `RPattern::dispatch`
+    pub overloads: RefCell<Vec<RFunction<'a>>>,
 }
 
 impl<'a> RPattern<'a> {
     pub fn new(name: String) -> RPattern<'a> {
         RPattern {
             name: name,
-            function: RefCell::new(None),
+            overloads: RefCell::new(Vec::new()),
         }
     }
 
-    pub fn set_function(&self, function: RFunction<'a>) {
-        *self.function.borrow_mut() = Some(function);
+    pub fn add_overload(&self, function: RFunction<'a>) {
+        //! Adds a declaration to the pattern; errors out if calls couldn't tell it apart from a previous one
+        if let Some(other) = self.overloads.borrow().iter().find(|other| overlaps(other, &function)) {
+            CompError::new(
+                164,
+                format!("Ambiguous declaration of {}: it accepts the same arguments as a previous one", self.name),
+                CompLocation::from(&function.location),
+            )
+            .append(format!("{} is first declared here", self.name), CompLocation::from(&other.location))
+            .print_and_exit();
+        }
+        self.overloads.borrow_mut().push(function);
+    }
+}
+
+fn overlaps<'a>(f: &RFunction<'a>, g: &RFunction<'a>) -> bool {
+    /*! Returns wether or not some calls match both `f` and `g` whatever their arguments are.
+    It is enough to look at the least number of arguments that both accept, as more arguments are only more types to tell them apart.
+    */
+    let n = f.required_args_n().max(g.required_args_n());
+    f.accepts_args_n(n)
+        && g.accepts_args_n(n)
+        && (0..n).all(|index| same_argtype(f.args.get(index), g.args.get(index)))
+}
+
+fn same_argtype<'a>(a: Option<&RFunctionArg<'a>>, b: Option<&RFunctionArg<'a>>) -> bool {
+    //! Arguments past the end of `args` go into the rest argument, which is untyped
+    let argtype = |arg: Option<&RFunctionArg<'a>>| arg.and_then(|arg| Some((arg.argtype.upgrade()?, arg.strictness.clone())));
+    match (argtype(a), argtype(b)) {
+        (None, None) => true,
+        (Some((a, a_strictness)), Some((b, b_strictness))) => Rc::ptr_eq(&a, &b) && a_strictness == b_strictness,
+        _ => false,
+    }
+}
+
+/** Verifies the named arguments of a call to `pat`.
+    A name may only be given once and, if the arguments of `pat` are already known, it must be one of them which hasn't been given positionally.
+    Calls to patterns whose arguments aren't known yet are verified when they are made.
+**/
+pub fn check_named_args<'a>(pat: &RPatRef<'a>, args: &RASTRef<'a>) {
+    let args = args.borrow();
+    let positional_n = args
+        .instructions
+        .iter()
+        .filter(|(node, _)| !matches!(node, RASTNode::NamedArg(_, _)))
+        .count();
+    let arg_names = pat.get_arg_names();
+    let mut given: Vec<(&String, &Location)> = Vec::new();
+
+    for (node, loc) in args.instructions.iter() {
+        if let RASTNode::NamedArg(name, _) = node {
+            if let Some((_, first_loc)) = given.iter().find(|(n, _)| *n == name) {
+                CompError::new(
+                    155,
+                    format!("Duplicate named argument {}", name),
+                    loc.into(),
+                )
+                .append(format!("{} is first given here", name), (*first_loc).into())
+                .print_and_exit();
+            }
+            if let Some(arg_names) = &arg_names {
+                match arg_names.iter().position(|n| n == name) {
+                    None => CompError::new(
+                        156,
+                        format!("Unknown named argument {} in call to {}", name, pat.get_name()),
+                        loc.into(),
+                    )
+                    .append(
+                        format!("{} takes as arguments: {}", pat.get_name(), arg_names.join(", ")),
+                        CompLocation::None,
+                    )
+                    .print_and_exit(),
+                    Some(index) if index < positional_n => CompError::new(
+                        155,
+                        format!("Duplicate named argument {}: it is already given positionally", name),
+                        loc.into(),
+                    )
+                    .print_and_exit(),
+                    Some(_) => {}
+                }
+            }
+            given.push((name, loc));
+        }
     }
 }
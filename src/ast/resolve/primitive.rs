@@ -0,0 +1,111 @@
+use super::*;
+use crate::interpreter::VariableValue;
+use std::fmt;
+
+/// The types of the values which aren't struct instances but on which methods may still be called, ie. `"abc".'len()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Primitive {
+    String,
+    Number,
+    Boolean,
+    Tuple,
+    Range,
+}
+
+impl Primitive {
+    pub fn from_name(name: &str) -> Option<Primitive> {
+        //! Returns the primitive type named `name` in `extends Name {...}`
+        match name {
+            "String" => Some(Primitive::String),
+            "Number" => Some(Primitive::Number),
+            "Boolean" => Some(Primitive::Boolean),
+            "Tuple" => Some(Primitive::Tuple),
+            "Range" => Some(Primitive::Range),
+            _ => None,
+        }
+    }
+
+    pub fn of(value: &VariableValue) -> Option<Primitive> {
+        match value {
+            VariableValue::String(_) => Some(Primitive::String),
+            VariableValue::Number(_) => Some(Primitive::Number),
+            VariableValue::Boolean(_) => Some(Primitive::Boolean),
+            VariableValue::Tuple(_) => Some(Primitive::Tuple),
+            VariableValue::Range(_) => Some(Primitive::Range),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Primitive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub type PrimitiveMethodsRef<'a> = Rc<RefCell<PrimitiveMethods<'a>>>;
+
+/** The methods of the primitive types: the built-in ones (see `internal::methods`), followed by those added by the `extends` blocks of the program.
+    It is shared by every method call of the program, as extensions may be declared after the calls using them.
+**/
+#[derive(Debug)]
+pub struct PrimitiveMethods<'a> {
+    pub methods: Vec<(Primitive, RPatRef<'a>)>,
+    pub extensions: Vec<RASTRef<'a>>, // keeps the bodies of the extensions alive
+}
+
+impl<'a> PrimitiveMethods<'a> {
+    pub fn new(methods: Vec<(Primitive, RPatRef<'a>)>) -> PrimitiveMethods<'a> {
+        PrimitiveMethods {
+            methods,
+            extensions: Vec::new(),
+        }
+    }
+
+    pub fn lookup(&self, primitive: Primitive, name: &str) -> Option<RPatRef<'a>> {
+        //! Looks up the method `name` of `primitive`; the latest one wins, so that extensions may override built-in methods
+        self.methods
+            .iter()
+            .rev()
+            .find(|(of, method)| *of == primitive && method.get_name() == name)
+            .map(|(_, method)| method.clone())
+    }
+}
+
+pub fn resolve_extension<'a>(
+    type_name: &TypeName,
+    body: AST<'a>,
+    loc: &Location<'a>,
+    res: &RASTRef<'a>,
+) {
+    //! Resolves `extends Name {...}`, adding the patterns declared within it to the methods of the primitive type `Name`
+    let primitive = match Primitive::from_name(&type_name.name) {
+        Some(primitive) => primitive,
+        None => CompError::new(
+            163,
+            format!("Cannot extend {}: it is not a primitive type", type_name.name),
+            CompLocation::from(loc),
+        )
+        .append(
+            String::from("Only String, Number, Boolean, Tuple and Range can be extended; methods of structs belong in their body"),
+            CompLocation::None,
+        )
+        .print_and_exit(),
+    };
+
+    for (node, loc) in body.instructions.iter() {
+        if let ASTNode::PatternDecl(p) = node {
+            if p.function.has_super {
+                err_misplaced_super(loc, format!("{} is a primitive type, whose methods have no parent", type_name.name));
+            }
+        }
+    }
+
+    let body = RAST::resolve(body, Rc::downgrade(res));
+    let methods = RAST::primitive_methods(res);
+    let mut methods = methods.borrow_mut();
+    for pattern in body.borrow().patterns.iter() {
+        methods.methods.push((primitive, pattern.clone()));
+    }
+    methods.extensions.push(body);
+}
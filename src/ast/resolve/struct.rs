@@ -1,12 +1,39 @@
 use super::*;
+use crate::interpreter::InstanceRef;
 use rusty_ulid::Ulid;
+use std::cell::OnceCell;
+
+/// An interpretation of a struct into another, declared with `from -> To: { ... }`
+#[derive(Debug)]
+pub struct RInterpretation<'a> {
+    pub into: RStructWeak<'a>,
+    /// The context declaring `from` and `to`, whose only instruction is the body of the interpretation
+    pub body: RASTRef<'a>,
+    /// The bytecode of the body, compiled by the VM on its first use
+    pub compiled: OnceCell<Rc<crate::vm::Chunk<'a>>>,
+}
 
 #[derive(Debug, Clone)]
 pub struct RStruct<'a> {
     id: u128,
     pub name: TypeName,
     pub context: Option<RASTRef<'a>>,
-    pub interpretations: Vec<(RStructWeak<'a>, RASTRef<'a>)>,
+    /// The struct which this struct extends, and whose members it inherits
+    pub parent: Option<RStructRef<'a>>,
+    pub interpretations: Vec<Rc<RInterpretation<'a>>>,
+    pub doc: Option<String>,
+    /// The static members declared in the struct's body, stored once for the whole program; they are initialised on first access
+    pub statics: Option<InstanceRef<'a>>,
+    /// The bytecode of the `let` declarations of the struct's body, compiled by the VM on the first instantiation
+    pub defaults: OnceCell<Rc<crate::vm::Chunk<'a>>>,
+    /// The members required by the interface, if this is one rather than a struct
+    pub interface: Option<Vec<InterfaceMember<'a>>>,
+    /// The interfaces which the struct implements, checked at resolution
+    pub interfaces: Vec<RStructRef<'a>>,
+    /// The variants of the enum, if this is one; each of them is a struct extending it
+    pub variants: Option<Vec<RStructWeak<'a>>>,
+    /// The tag of the variant, if this is the variant of an enum
+    pub tag: Option<usize>,
 }
 
 impl<'a> RStruct<'a> {
@@ -15,7 +42,15 @@ impl<'a> RStruct<'a> {
             id: Ulid::generate().into(),
             name: name,
             context: None,
+            parent: None,
             interpretations: Vec::new(),
+            doc: None,
+            statics: None,
+            defaults: OnceCell::new(),
+            interface: None,
+            interfaces: Vec::new(),
+            variants: None,
+            tag: None,
         }
     }
 
@@ -37,10 +72,15 @@ impl<'a> RStruct<'a> {
         init.borrow_mut()
             .instructions
             .push((RASTNode::Block(body), loc));
-        self.interpretations.push((to, init));
+        self.interpretations.push(Rc::new(RInterpretation {
+            into: to,
+            body: init,
+            compiled: OnceCell::new(),
+        }));
     }
 
     pub fn get_method(&self, name: String) -> Option<RPatRef<'a>> {
+        //! Looks up a method, first in the struct's body and then in its ancestors', so that methods may be overriden
         if let Some(ctx) = &self.context {
             for pattern in &ctx.borrow().patterns {
                 if pattern.get_name() == name {
@@ -48,11 +88,81 @@ impl<'a> RStruct<'a> {
                 }
             }
         }
+        self.parent.as_ref().and_then(|p| p.borrow().get_method(name))
+    }
+
+    pub fn get_interpretation(&self, into: &RStructRef<'a>) -> Option<Rc<RInterpretation<'a>>> {
+        //! Looks up the interpretation into `into`, first in the struct and then in its ancestors
+        self.interpretations
+            .iter()
+            .find(|x| x.into.upgrade().is_some_and(|y| *y == **into))
+            .cloned()
+            .or_else(|| self.parent.as_ref().and_then(|p| p.borrow().get_interpretation(into)))
+    }
+
+    pub fn has_field(&self, name: &str) -> bool {
+        //! Returns wether or not `name` is declared in the struct's body or in the body of one of its ancestors, using `let`
+        match &self.context {
+            Some(ctx) => {
+                ctx.borrow().variables.iter().any(|var| var.borrow().name == name && !var.borrow().is_static)
+                    || self.parent.as_ref().is_some_and(|p| p.borrow().has_field(name))
+            }
+            None => true,
+        }
+    }
+
+    pub fn has_static(&self, name: &str) -> bool {
+        //! Returns wether or not `name` is declared in the struct's own body, using `static`
+        self.context
+            .as_ref()
+            .is_some_and(|ctx| ctx.borrow().variables.iter().any(|var| var.borrow().name == name && var.borrow().is_static))
+    }
+
+    pub fn fields(&self) -> Vec<String> {
+        //! Returns the names of the fields declared in the struct's own body, in order
+        match &self.context {
+            Some(ctx) => ctx
+                .borrow()
+                .variables
+                .iter()
+                .filter(|var| !var.borrow().is_static)
+                .map(|var| var.borrow().name.clone())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn is_descendant_of(&self, other: &RStructRef<'a>) -> bool {
+        //! Returns wether or not `self` is `other` or extends it, directly or not
+        self.id == other.borrow().id
+            || self.parent.as_ref().is_some_and(|p| p.borrow().is_descendant_of(other))
+    }
+
+    pub fn conforms_to(&self, other: &RStructRef<'a>) -> bool {
+        //! Returns wether or not instances of `self` may be used where `other` is expected: `self` extends `other`, or it or one of its ancestors implements the interface `other`
+        self.is_descendant_of(other)
+            || self.interfaces.iter().any(|i| Rc::ptr_eq(i, other))
+            || self.parent.as_ref().is_some_and(|p| p.borrow().conforms_to(other))
+    }
+
+    pub fn super_of(st: &RStructRef<'a>, context: &RASTRef<'a>) -> Option<RStructRef<'a>> {
+        //! Returns the struct to which `super` refers within the methods declared in `context`: the parent of `st` or of the ancestor of `st` whose body is `context`
+        let mut current = Some(st.clone());
+        while let Some(st) = current {
+            let st = st.borrow();
+            if st.context.as_ref().is_some_and(|ctx| Rc::ptr_eq(ctx, context)) {
+                return st.parent.clone();
+            }
+            current = st.parent.clone();
+        }
         None
     }
 
-    pub fn is_subtype_of(&self, other: RStructRef<'_>) -> bool {
-        //! Asserts that self.context has been set
+    pub fn is_subtype_of(&self, other: RStructRef<'a>) -> bool {
+        //! Asserts that self.context has been set; structs extending `other` are always subtypes of it, while other structs are compared member-wise
+        if self.is_descendant_of(&other) {
+            return true;
+        }
         self.context.as_ref().map(|ctx_self| other.borrow().context.as_ref().map(|ctx_other| {
             for pattern_self in &ctx_self.borrow().patterns {
                 let mut found = false;
@@ -82,8 +192,11 @@ impl<'a> RStruct<'a> {
         })).unwrap_or(Some(false)).unwrap()
     }
 
-    pub fn can_turn_into(&self, other: RStructRef<'_>) -> bool {
-        //! Asserts that self.context has been set
+    pub fn can_turn_into(&self, other: RStructRef<'a>) -> bool {
+        //! Asserts that self.context has been set; instances may always be turned into one of the structs that their own struct extends
+        if self.is_descendant_of(&other) {
+            return true;
+        }
         self.context.as_ref().map(|ctx_self| other.borrow().context.as_ref().map(|ctx_other| {
             for variable_self in &ctx_self.borrow().variables {
                 let mut found = false;
@@ -102,6 +215,110 @@ impl<'a> RStruct<'a> {
     }
 }
 
+/** Declares the enum `def` and its variants in `res`.
+    Each variant is a struct extending the enum, whose fields are those of its payload; they are resolved right away, so that variants may be built before the enum's declaration is reached.
+**/
+pub fn resolve_enum<'a>(def: &EnumDef<'a>, res: &RASTRef<'a>, loc: &Location<'a>) {
+    let st = Rc::new(RefCell::new(RStruct::new(def.name.clone())));
+    st.borrow_mut().doc = def.doc.clone();
+    st.borrow_mut().context = Some(RAST::resolve(
+        AST {
+            instructions: Vec::new(),
+            kind: ASTKind::Struct,
+        },
+        Rc::downgrade(res),
+    ));
+
+    let mut variants = Vec::with_capacity(def.variants.len());
+    for (tag, variant) in def.variants.iter().enumerate() {
+        let body = AST {
+            instructions: variant
+                .fields
+                .iter()
+                .map(|field| (ASTNode::VariableDecl(field.clone()), variant.location.clone()))
+                .collect(),
+            kind: ASTKind::Struct,
+        };
+        let mut variant_st = RStruct::new(variant.name.clone());
+        variant_st.parent = Some(st.clone());
+        variant_st.tag = Some(tag);
+        variant_st.context = Some(RAST::resolve(body, Rc::downgrade(res)));
+        let variant_st = Rc::new(RefCell::new(variant_st));
+        variants.push(Rc::downgrade(&variant_st));
+        declare_type(res, variant_st, &variant.location);
+    }
+    st.borrow_mut().variants = Some(variants);
+    declare_type(res, st, loc);
+}
+
+pub fn declare_type<'a>(res: &RASTRef<'a>, st: RStructRef<'a>, loc: &Location<'a>) {
+    //! Registers `st` in the scope `res`; errors out if another struct, interface, enum or variant of the scope has the same name
+    let name = st.borrow().name.clone();
+    if res.borrow().structs.iter().any(|other| other.borrow().name == name) {
+        CompError::new(
+            165,
+            format!("Type {} is already declared in this scope", name),
+            CompLocation::from(loc),
+        )
+        .append(
+            String::from("The variants of an enum are declared alongside it, so they may not share the name of another type or variant"),
+            CompLocation::None,
+        )
+        .print_and_exit();
+    }
+    res.borrow_mut().structs.push(st);
+}
+
+/** Asserts that `st` implements `interface`, as required by `implements`: it must declare (or inherit) each of the interface's fields, as well as each of its patterns with the same number of arguments.
+    The missing members are all listed in the error.
+**/
+pub fn check_interface<'a>(st: &RStructRef<'a>, interface: &RStructRef<'a>, loc: &Location<'a>) {
+    let members = match &interface.borrow().interface {
+        Some(members) => members.clone(),
+        None => CompError::new(
+            159,
+            format!("{} is not an interface, so it cannot be implemented", interface.borrow().name),
+            CompLocation::from(loc),
+        )
+        .print_and_exit(),
+    };
+
+    let mut missing = Vec::new();
+    for member in members {
+        match &member {
+            InterfaceMember::Variable(name, _) if !st.borrow().has_field(name) => missing.push(member),
+            InterfaceMember::Pattern(name, arity, _) => {
+                let method = st.borrow().get_method(name.clone());
+                if !method.is_some_and(|m| m.takes_args_n(*arity)) {
+                    missing.push(member);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !missing.is_empty() {
+        let mut error = CompError::new(
+            158,
+            format!("Struct {} does not implement interface {}", st.borrow().name, interface.borrow().name),
+            CompLocation::from(loc),
+        );
+        for member in missing {
+            error = match member {
+                InterfaceMember::Variable(name, loc) => error.append(
+                    format!("Missing field {}, declared here", name),
+                    CompLocation::from(loc),
+                ),
+                InterfaceMember::Pattern(name, arity, loc) => error.append(
+                    format!("Missing pattern {} taking {} argument(s), declared here", name, arity),
+                    CompLocation::from(loc),
+                ),
+            };
+        }
+        error.print_and_exit();
+    }
+}
+
 impl<'a> PartialEq for RStruct<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id
@@ -4,11 +4,78 @@ use super::*;
 pub struct RSymbol {
     // Only used during interpretation
     pub name: String,
+    /// The line and char at which the symbol is defined, if it is a constant
+    pub constant: Option<(usize, usize)>,
+    /// Wether the symbol is a static member of the struct whose body declares it, rather than a field
+    pub is_static: bool,
 }
 
 impl RSymbol {
     pub fn new(name: String) -> RSymbol {
-        RSymbol { name }
+        RSymbol {
+            name,
+            constant: None,
+            is_static: false,
+        }
+    }
+
+    pub fn new_constant(name: String, loc: &Location) -> RSymbol {
+        RSymbol {
+            name,
+            constant: Some((loc.line, loc.ch)),
+            is_static: false,
+        }
+    }
+
+    pub fn new_static(name: String) -> RSymbol {
+        RSymbol {
+            name,
+            constant: None,
+            is_static: true,
+        }
+    }
+}
+
+pub fn check_mutable(var: &RSymRef, loc: &Location) {
+    //! Errors out if `var` is a constant, as it is assigned to at `loc`
+    if let Some((line, ch)) = var.ast_ref.borrow().constant {
+        CompError::new(
+            162,
+            format!("Cannot assign to constant {}", var.name),
+            CompLocation::from(loc),
+        )
+        .append(
+            format!("{} is defined as a constant here", var.name),
+            CompLocation::Char(loc.src, line, ch),
+        )
+        .print_and_exit();
+    }
+}
+
+pub fn check_member_kind(var: &RSymRef, is_static: bool, loc: &Location) {
+    //! Errors out if `var`, a member of a struct, is declared both as a field and as a static member, as it is at `loc`
+    if var.ast_ref.borrow().is_static != is_static {
+        CompError::new(
+            166,
+            format!("{} is declared both as a field and as a static member of the struct", var.name),
+            CompLocation::from(loc),
+        )
+        .print_and_exit();
+    }
+}
+
+pub fn check_redeclared_constant(var: &RSymRef, loc: &Location) {
+    //! Errors out if the constant declared at `loc` resolved to `var`, an earlier declaration of the same name
+    let constant = var.ast_ref.borrow().constant;
+    match constant {
+        Some((line, ch)) if (line, ch) == (loc.line, loc.ch) => {}
+        Some(_) => check_mutable(var, loc),
+        None => CompError::new(
+            162,
+            format!("Cannot declare constant {}: it is already declared as a variable in this scope", var.name),
+            CompLocation::from(loc),
+        )
+        .print_and_exit(),
     }
 }
 
@@ -18,16 +85,19 @@ pub struct RSymRef {
     pub name: String,
     pub depth: usize,
     pub ulid: u128,
+    /// The index of the variable within the `variables` of the RAST declaring it, and thus within its `Context`
+    pub slot: usize,
 }
 
 impl RSymRef {
-    pub fn new(ast_ref: Rc<RefCell<RSymbol>>, depth: usize, ulid: u128) -> RSymRef {
+    pub fn new(ast_ref: Rc<RefCell<RSymbol>>, depth: usize, ulid: u128, slot: usize) -> RSymRef {
         let name = ast_ref.borrow().name.clone();
         RSymRef {
             name,
             ast_ref,
             depth,
             ulid,
+            slot,
         }
     }
 }
@@ -0,0 +1,43 @@
+use super::*;
+
+/// A struct declaration: `Name: struct extends Parent implements Interface {...}`
+#[derive(Debug, Clone)]
+pub struct StructDef<'a> {
+    pub name: TypeName,
+    pub parent: Option<TypeName>,
+    pub interfaces: Vec<(TypeName, Location<'a>)>,
+    pub body: AST<'a>,
+    pub doc: Option<String>,
+}
+
+/// An interface declaration: `Name: interface {...}`, listing the members that the structs implementing it must have
+#[derive(Debug, Clone)]
+pub struct InterfaceDef<'a> {
+    pub name: TypeName,
+    pub members: Vec<InterfaceMember<'a>>,
+    pub doc: Option<String>,
+}
+
+/// An enum declaration: `Name: enum { Variant(field, ...), ... }`
+#[derive(Debug, Clone)]
+pub struct EnumDef<'a> {
+    pub name: TypeName,
+    pub variants: Vec<VariantDef<'a>>,
+    pub doc: Option<String>,
+}
+
+/// A variant of an enum, along with the names of the fields of its payload
+#[derive(Debug, Clone)]
+pub struct VariantDef<'a> {
+    pub name: TypeName,
+    pub fields: Vec<String>,
+    pub location: Location<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub enum InterfaceMember<'a> {
+    /// `let name`
+    Variable(String, Location<'a>),
+    /// `'name: (args)`, along with its number of arguments, flags excluded
+    Pattern(String, usize, Location<'a>),
+}
@@ -0,0 +1,252 @@
+// Internal methods of the primitive types, ie. `"abc".'len()`
+
+use super::*;
+
+pub fn std_methods<'a>() -> Vec<(Primitive, RPatRef<'a>)> {
+    let mut res = Vec::new();
+
+    // String
+
+    add_method(&mut res, Primitive::String, "'len", 0, |this, _, _, _| {
+        VariableValue::Number(string(&this).chars().count() as f64)
+    });
+
+    add_method(&mut res, Primitive::String, "'upper", 0, |this, _, _, _| {
+        VariableValue::String(string(&this).to_uppercase())
+    });
+
+    add_method(&mut res, Primitive::String, "'lower", 0, |this, _, _, _| {
+        VariableValue::String(string(&this).to_lowercase())
+    });
+
+    add_method(&mut res, Primitive::String, "'trim", 0, |this, _, _, _| {
+        VariableValue::String(string(&this).trim().to_string())
+    });
+
+    add_method(&mut res, Primitive::String, "'contains", 1, |this, args, loc, _| {
+        let needle = string_arg(&args[0], "'contains", &loc);
+        VariableValue::Boolean(string(&this).contains(needle))
+    });
+
+    add_method(&mut res, Primitive::String, "'split", 1, |this, args, loc, _| {
+        let separator = string_arg(&args[0], "'split", &loc);
+        VariableValue::Tuple(
+            string(&this)
+                .split(separator)
+                .map(|part| VariableValue::String(part.to_string()))
+                .collect(),
+        )
+    });
+
+    add_method(&mut res, Primitive::String, "'repeat", 1, |this, args, loc, _| {
+        let n = number_arg(&args[0], "'repeat", &loc);
+        VariableValue::String(string(&this).repeat(n.max(0.0) as usize))
+    });
+
+    // Number
+
+    add_method(&mut res, Primitive::Number, "'pow", 1, |this, args, loc, _| {
+        VariableValue::Number(number(&this).powf(number_arg(&args[0], "'pow", &loc)))
+    });
+
+    add_method(&mut res, Primitive::Number, "'sqrt", 0, |this, _, _, _| {
+        VariableValue::Number(number(&this).sqrt())
+    });
+
+    add_method(&mut res, Primitive::Number, "'abs", 0, |this, _, _, _| {
+        VariableValue::Number(number(&this).abs())
+    });
+
+    add_method(&mut res, Primitive::Number, "'floor", 0, |this, _, _, _| {
+        VariableValue::Number(number(&this).floor())
+    });
+
+    add_method(&mut res, Primitive::Number, "'ceil", 0, |this, _, _, _| {
+        VariableValue::Number(number(&this).ceil())
+    });
+
+    add_method(&mut res, Primitive::Number, "'round", 0, |this, _, _, _| {
+        VariableValue::Number(number(&this).round())
+    });
+
+    add_method(&mut res, Primitive::Number, "'min", 1, |this, args, loc, _| {
+        VariableValue::Number(number(&this).min(number_arg(&args[0], "'min", &loc)))
+    });
+
+    add_method(&mut res, Primitive::Number, "'max", 1, |this, args, loc, _| {
+        VariableValue::Number(number(&this).max(number_arg(&args[0], "'max", &loc)))
+    });
+
+    // Boolean
+
+    add_method(&mut res, Primitive::Boolean, "'not", 0, |this, _, _, _| {
+        VariableValue::Boolean(!is_truthy(&this))
+    });
+
+    // Tuple
+
+    add_method(&mut res, Primitive::Tuple, "'len", 0, |this, _, _, _| {
+        VariableValue::Number(tuple(this).len() as f64)
+    });
+
+    add_method(&mut res, Primitive::Tuple, "'map", 1, |this, args, loc, contexes| {
+        map(tuple(this), &args[0], "'map", loc, contexes)
+    });
+
+    add_method(&mut res, Primitive::Tuple, "'filter", 1, |this, args, loc, contexes| {
+        let (fun, closure) = function_arg(&args[0], "'filter", &loc);
+        let mut kept = Vec::new();
+        for x in tuple(this) {
+            let keep = fun.call(vec![x.clone()], loc.clone(), contexes, closure.clone());
+            if signal::is_raised(contexes) {
+                return VariableValue::Nil;
+            }
+            if is_truthy(&keep) {
+                kept.push(x);
+            }
+        }
+        VariableValue::Tuple(kept)
+    });
+
+    add_method(&mut res, Primitive::Tuple, "'fold", 2, |this, args, loc, contexes| {
+        let mut args = args.into_iter();
+        let mut acc = args.next().unwrap();
+        let (fun, closure) = function_arg(&args.next().unwrap(), "'fold", &loc);
+        for x in tuple(this) {
+            acc = fun.call(vec![acc, x], loc.clone(), contexes, closure.clone());
+            if signal::is_raised(contexes) {
+                return VariableValue::Nil;
+            }
+        }
+        acc
+    });
+
+    add_method(&mut res, Primitive::Tuple, "'contains", 1, |this, args, _, _| {
+        VariableValue::Boolean(tuple(this).contains(&args[0]))
+    });
+
+    add_method(&mut res, Primitive::Tuple, "'reverse", 0, |this, _, _, _| {
+        VariableValue::Tuple(tuple(this).into_iter().rev().collect())
+    });
+
+    // Range
+
+    add_method(&mut res, Primitive::Range, "'len", 0, |this, _, _, _| {
+        VariableValue::Number(range(&this).len() as f64)
+    });
+
+    add_method(&mut res, Primitive::Range, "'contains", 1, |this, args, loc, _| {
+        VariableValue::Boolean(range(&this).contains(number_arg(&args[0], "'contains", &loc)))
+    });
+
+    add_method(&mut res, Primitive::Range, "'map", 1, |this, args, loc, contexes| {
+        let values = range(&this).iter().map(VariableValue::Number).collect();
+        map(values, &args[0], "'map", loc, contexes)
+    });
+
+    add_method(&mut res, Primitive::Range, "'tuple", 0, |this, _, _, _| {
+        VariableValue::Tuple(range(&this).iter().map(VariableValue::Number).collect())
+    });
+
+    res
+}
+
+fn add_method<'a, F>(
+    methods: &mut Vec<(Primitive, RPatRef<'a>)>,
+    of: Primitive,
+    name: &str,
+    args_n: usize,
+    fun: F,
+) where
+    F: Fn(VariableValue<'a>, Vec<VariableValue<'a>>, Location<'a>, &Vec<ContextRef<'a>>) -> VariableValue<'a> + 'static,
+{
+    methods.push((of, Rc::new(IntMethod::new(name.to_string(), args_n, fun))));
+}
+
+fn map<'a>(
+    values: Vec<VariableValue<'a>>,
+    fun: &VariableValue<'a>,
+    name: &str,
+    loc: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    //! Calls `fun` on each of `values`, stopping if a signal is raised by one of the calls
+    let (fun, closure) = function_arg(fun, name, &loc);
+    let mut res = Vec::with_capacity(values.len());
+    for x in values {
+        res.push(fun.call(vec![x], loc.clone(), contexes, closure.clone()));
+        if signal::is_raised(contexes) {
+            return VariableValue::Nil;
+        }
+    }
+    VariableValue::Tuple(res)
+}
+
+/* The values on which the methods are called; they are only called on values of their primitive type */
+
+fn string<'b>(value: &'b VariableValue) -> &'b str {
+    match value {
+        VariableValue::String(x) => x,
+        _ => unreachable!("Expected a String method to be called on a string"),
+    }
+}
+
+fn number(value: &VariableValue) -> f64 {
+    match value {
+        VariableValue::Number(x) => *x,
+        _ => unreachable!("Expected a Number method to be called on a number"),
+    }
+}
+
+fn tuple(value: VariableValue) -> Vec<VariableValue> {
+    match value {
+        VariableValue::Tuple(x) => x,
+        _ => unreachable!("Expected a Tuple method to be called on a tuple"),
+    }
+}
+
+fn range(value: &VariableValue) -> Range {
+    match value {
+        VariableValue::Range(x) => *x,
+        _ => unreachable!("Expected a Range method to be called on a range"),
+    }
+}
+
+/* The arguments of the methods */
+
+fn err_argtype(expected: &str, method: &str, value: &VariableValue, loc: &Location) -> ! {
+    CompError::new(
+        211,
+        format!(
+            "Mismatching argument type: expected {} as argument of {}, got {}",
+            expected, method, value
+        ),
+        CompLocation::from(loc),
+    )
+    .print_and_exit()
+}
+
+fn string_arg<'b>(value: &'b VariableValue, method: &str, loc: &Location) -> &'b str {
+    match value {
+        VariableValue::String(x) => x,
+        x => err_argtype("a string", method, x, loc),
+    }
+}
+
+fn number_arg(value: &VariableValue, method: &str, loc: &Location) -> f64 {
+    match value {
+        VariableValue::Number(x) => *x,
+        x => err_argtype("a number", method, x, loc),
+    }
+}
+
+fn function_arg<'a>(
+    value: &VariableValue<'a>,
+    method: &str,
+    loc: &Location,
+) -> (RPatRef<'a>, Vec<(String, VariableValue<'a>)>) {
+    match value {
+        VariableValue::Function(fun, closure) => (fun.clone(), closure.clone()),
+        x => err_argtype("a function", method, x, loc),
+    }
+}
@@ -1,5 +1,6 @@
 // Internal patterns and constants
 
+pub mod methods;
 pub mod pattern;
 
 pub use super::*;
@@ -7,14 +8,16 @@ pub use crate::interpreter::*;
 use std::cell::RefCell;
 use std::rc::Weak;
 
+pub use methods::*;
 pub use pattern::*;
 
 thread_local!(pub static TEST_LOG: RefCell<String> = RefCell::new(String::new()));
 
 pub fn std_rast<'a>() -> RAST<'a> {
     let mut res = RAST::new(Weak::new(), ASTKind::Block);
+    res.primitive_methods = Some(Rc::new(RefCell::new(PrimitiveMethods::new(std_methods()))));
 
-    add_pattern(&mut res, "#println", |args, _, _| {
+    add_pattern(&mut res, "#println", None, |args, _, _| {
         println!(
             "{}",
             args.iter()
@@ -25,7 +28,14 @@ pub fn std_rast<'a>() -> RAST<'a> {
         VariableValue::Nil
     });
 
-    add_pattern(&mut res, "#test_log", |args, _, _| {
+    add_pattern(&mut res, "#debug", None, |args, _, _| {
+        for arg in args.iter() {
+            println!("{}", arg.pretty());
+        }
+        VariableValue::Nil
+    });
+
+    add_pattern(&mut res, "#test_log", None, |args, _, _| {
         TEST_LOG.with(|s| {
             s.borrow_mut().push_str(
                 &args
@@ -39,62 +49,88 @@ pub fn std_rast<'a>() -> RAST<'a> {
         VariableValue::Nil
     });
 
-    add_pattern(&mut res, "#if", |args, loc, contexes| {
+    add_branch_pattern(&mut res, "#if", Some(2), |args, _| {
         if args.len() < 2 {
-            return VariableValue::Nil;
+            return Branch::Value(VariableValue::Nil);
         }
         let mut iter = args.into_iter();
         if is_truthy(&iter.next().unwrap()) {
-            match iter.next().unwrap() {
-                VariableValue::Function(fun, closure) => {
-                    // TODO: give Callable a n_args() method
-                    fun.call(vec![], loc, contexes, closure)
-                }
-                x => x,
-            }
+            Branch::Take(iter.next().unwrap())
         } else {
-            VariableValue::Bail
+            Branch::Value(VariableValue::Bail)
         }
     });
 
-    add_pattern(&mut res, "#else", |args, loc, contexes| {
+    add_branch_pattern(&mut res, "#else", Some(1), |args, contexes| {
         let last_value = contexes.last().unwrap().borrow().last_value.clone();
         if args.len() < 1 {
-            return VariableValue::Nil;
+            return Branch::Value(VariableValue::Nil);
         }
 
         if has_bailed(&last_value) {
-            match args.into_iter().next().unwrap() {
-                VariableValue::Function(fun, closure) => fun.call(vec![], loc, contexes, closure),
-                x => x,
-            }
+            Branch::Take(args.into_iter().next().unwrap())
         } else {
-            last_value
+            Branch::Value(last_value)
         }
     });
 
-    add_pattern(&mut res, "#elseif", |args, loc, contexes| {
+    add_branch_pattern(&mut res, "#elseif", Some(2), |args, contexes| {
         let last_value = contexes.last().unwrap().borrow().last_value.clone();
         if args.len() < 2 {
-            return VariableValue::Nil;
+            return Branch::Value(VariableValue::Nil);
         }
         let mut iter = args.into_iter();
 
         if has_bailed(&last_value) {
             if is_truthy(&iter.next().unwrap()) { // 1st argument: condition
-                match iter.next().unwrap() { // 2nd argument
-                    VariableValue::Function(fun, closure) => fun.call(vec![], loc, contexes, closure),
-                    x => x,
-                }
+                Branch::Take(iter.next().unwrap()) // 2nd argument
             } else {
-                last_value
+                Branch::Value(last_value)
             }
         } else {
-            last_value
+            Branch::Value(last_value)
         }
     });
 
-    add_pattern(&mut res, "#bail", |args, _, _| {
+    add_pattern(&mut res, "#arity", Some(1), |args, _loc, _contexes| {
+        if args.len() != 1 {
+            return VariableValue::Nil;
+        }
+
+        if let VariableValue::Function(fun, _) = &args[0] {
+            fun.get_args_n()
+                .map(|n| VariableValue::Number(n as f64))
+                .unwrap_or(VariableValue::Nil)
+        } else {
+            VariableValue::Nil
+        }
+    });
+
+    add_pattern(&mut res, "#help", Some(1), |args, _loc, _contexes| {
+        if args.len() != 1 {
+            return VariableValue::Nil;
+        }
+
+        let doc = match &args[0] {
+            VariableValue::Function(fun, _) => fun.get_doc(),
+            VariableValue::Type(st) | VariableValue::Instance(st, _) | VariableValue::Super(_, st, _) => {
+                st.borrow().doc.clone()
+            }
+            _ => None,
+        };
+        match doc {
+            Some(doc) => {
+                println!("{}", doc);
+                VariableValue::String(doc)
+            }
+            None => {
+                println!("No documentation for {}", args[0]);
+                VariableValue::Nil
+            }
+        }
+    });
+
+    add_pattern(&mut res, "#bail", None, |args, _, _| {
         if args.len() == 0 {
             VariableValue::Bail
         } else {
@@ -107,7 +143,7 @@ pub fn std_rast<'a>() -> RAST<'a> {
         }
     });
 
-    add_pattern(&mut res, "#unbail", |args, _, _| {
+    add_pattern(&mut res, "#unbail", Some(1), |args, _, _| {
         if args.len() == 1 && has_bailed(&args[0]) {
             match args.into_iter().next() {
                 Some(VariableValue::Tuple(vec)) => {
@@ -122,33 +158,49 @@ pub fn std_rast<'a>() -> RAST<'a> {
         }
     });
 
-    add_pattern(&mut res, "#for", |args, loc, contexes| {
-        if args.len() < 3 {
+    add_pattern(&mut res, "#range", None, |args, loc, _contexes| {
+        match args.as_slice() {
+            [VariableValue::Number(from), VariableValue::Number(to)] => {
+                VariableValue::Range(Range::new(*from, *to, None, &loc))
+            }
+            [VariableValue::Number(from), VariableValue::Number(to), VariableValue::Number(step)] => {
+                VariableValue::Range(Range::new(*from, *to, Some(*step), &loc))
+            }
             // TODO: error out
-            return VariableValue::Nil;
+            _ => VariableValue::Nil,
         }
-        let mut iter = args.into_iter();
-        let from_raw = iter.next().unwrap();
-        let to_raw = iter.next().unwrap();
-        let callback_raw = iter.next().unwrap();
+    });
+
+    // #for(from, to, fn), #for(from, to, step, fn) or #for(range, fn); without a step, #for(from, to, fn) counts up and does nothing if `to < from`
+    add_pattern(&mut res, "#for", None, |args, loc, contexes| {
+        let mut args = args;
+        let callback_raw = args.pop().unwrap_or(VariableValue::Nil);
+        let range = match args.as_slice() {
+            [VariableValue::Range(range)] => *range,
+            [VariableValue::Number(from), VariableValue::Number(to)] => {
+                Range::new(*from, *to, Some(1.0), &loc)
+            }
+            [VariableValue::Number(from), VariableValue::Number(to), VariableValue::Number(step)] => {
+                Range::new(*from, *to, Some(*step), &loc)
+            }
+            // TODO: error out
+            _ => return VariableValue::Nil,
+        };
         if let VariableValue::Function(callback, closure) = callback_raw {
-            if let (VariableValue::Number(from), VariableValue::Number(to)) = (from_raw, to_raw) {
-                let mut last_value = VariableValue::Nil;
-                for x in (from as usize)..((to + 1f64) as usize) {
-                    let res = callback.call(vec![VariableValue::Number(x as f64)], loc.clone(), contexes, closure.clone());
-                    if has_bailed(&res) {
-                        return res;
-                    } else {
-                        last_value = res;
-                    }
+            let mut last_value = VariableValue::Nil;
+            for x in range.iter() {
+                let res = callback.call(vec![VariableValue::Number(x)], loc.clone(), contexes, closure.clone());
+                match loop_flow(res, contexes) {
+                    Flow::Next(res) => last_value = res,
+                    Flow::Exit(res) => return res,
                 }
-                return last_value;
             }
+            return last_value;
         }
         return VariableValue::Nil;
     });
 
-    add_pattern(&mut res, "#loop", |args, loc, contexes| {
+    add_pattern(&mut res, "#loop", Some(1), |args, loc, contexes| {
         if args.len() < 1 {
             // TODO: error out
             return VariableValue::Nil;
@@ -157,15 +209,65 @@ pub fn std_rast<'a>() -> RAST<'a> {
         if let VariableValue::Function(callback, closure) = callback_raw {
             loop {
                 let res = callback.call(vec![], loc.clone(), contexes, closure.clone());
+                // as it has no bound, #loop() also stops once its callback yields `[bail]`
                 if has_bailed(&res) {
                     return res;
                 }
+                if let Flow::Exit(res) = loop_flow(res, contexes) {
+                    return res;
+                }
             }
         }
         VariableValue::Nil
     });
 
-    add_pattern(&mut res, "#do", |args, loc, contexes| {
+    add_pattern(&mut res, "#while", Some(2), |args, loc, contexes| {
+        if args.len() < 2 {
+            // TODO: error out
+            return VariableValue::Nil;
+        }
+        let mut iter = args.into_iter();
+        let condition_raw = iter.next().unwrap();
+        let callback_raw = iter.next().unwrap();
+        if let (
+            VariableValue::Function(condition, condition_closure),
+            VariableValue::Function(callback, closure),
+        ) = (condition_raw, callback_raw)
+        {
+            let mut last_value = VariableValue::Nil;
+            loop {
+                let holds = condition.call(vec![], loc.clone(), contexes, condition_closure.clone());
+                if signal::is_raised(contexes) || !is_truthy(&holds) {
+                    return last_value;
+                }
+                let res = callback.call(vec![], loc.clone(), contexes, closure.clone());
+                match loop_flow(res, contexes) {
+                    Flow::Next(res) => last_value = res,
+                    Flow::Exit(res) => return res,
+                }
+            }
+        }
+        VariableValue::Nil
+    });
+
+    add_pattern(&mut res, "#return", Some(1), |args, _loc, contexes| {
+        let value = args.into_iter().next().unwrap_or(VariableValue::Nil);
+        signal::raise(Signal::Return(value), contexes);
+        VariableValue::Nil
+    });
+
+    add_pattern(&mut res, "#break", Some(1), |args, loc, contexes| {
+        let value = args.into_iter().next().unwrap_or(VariableValue::Nil);
+        signal::raise(Signal::Break(value, loc), contexes);
+        VariableValue::Nil
+    });
+
+    add_pattern(&mut res, "#continue", Some(0), |_args, loc, contexes| {
+        signal::raise(Signal::Continue(loc), contexes);
+        VariableValue::Nil
+    });
+
+    add_pattern(&mut res, "#do", Some(1), |args, loc, contexes| {
         if args.len() < 1 {
             // TODO: error out
             return VariableValue::Nil;
@@ -177,7 +279,7 @@ pub fn std_rast<'a>() -> RAST<'a> {
         VariableValue::Nil
     });
 
-    add_pattern(&mut res, "#last", |args, _loc, _contexes| {
+    add_pattern(&mut res, "#last", Some(1), |args, _loc, _contexes| {
         if args.len() != 1 {
             return VariableValue::Nil;
         }
@@ -189,7 +291,7 @@ pub fn std_rast<'a>() -> RAST<'a> {
         }
     });
 
-    add_pattern(&mut res, "#first", |args, _loc, _contexes| {
+    add_pattern(&mut res, "#first", Some(1), |args, _loc, _contexes| {
         if args.len() != 1 {
             return VariableValue::Nil;
         }
@@ -201,7 +303,7 @@ pub fn std_rast<'a>() -> RAST<'a> {
         }
     });
 
-    add_pattern(&mut res, "#push", |args, _loc, _contexes| {
+    add_pattern(&mut res, "#push", Some(2), |args, _loc, _contexes| {
         if args.len() != 2 {
             return VariableValue::Nil;
         }
@@ -215,7 +317,7 @@ pub fn std_rast<'a>() -> RAST<'a> {
         }
     });
 
-    add_pattern(&mut res, "#pop", |args, _loc, _contexes| {
+    add_pattern(&mut res, "#pop", Some(1), |args, _loc, _contexes| {
         if args.len() != 1 {
             return VariableValue::Nil;
         }
@@ -230,12 +332,82 @@ pub fn std_rast<'a>() -> RAST<'a> {
     res
 }
 
-fn add_pattern<'a, F: 'static>(rast: &mut RAST<'a>, name: &str, fun: F)
+fn add_pattern<'a, F: 'static>(rast: &mut RAST<'a>, name: &str, args_n: Option<usize>, fun: F)
 where
     F: Fn(Vec<VariableValue<'a>>, Location<'a>, &Vec<ContextRef<'a>>) -> VariableValue<'a>,
 {
     rast.patterns
-        .push(Rc::new(IntPattern::new(name.to_string(), fun)));
+        .push(Rc::new(IntPattern::new(name.to_string(), args_n, fun)));
+}
+
+fn add_branch_pattern<'a, F>(rast: &mut RAST<'a>, name: &str, args_n: Option<usize>, fun: F)
+where
+    F: Fn(Vec<VariableValue<'a>>, &Vec<ContextRef<'a>>) -> Branch<'a> + 'static,
+{
+    rast.patterns
+        .push(Rc::new(BranchPattern::new(name.to_string(), args_n, fun)));
+}
+
+/** Returns the value of an `#if`/`#elseif`/`#else` branch.
+    Functions of arity 0 (whose arguments all have a default value, if any) or of varying arity are called; other values, including functions which require arguments, are returned as-is.
+**/
+fn call_branch<'a>(
+    value: VariableValue<'a>,
+    loc: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    match value {
+        VariableValue::Function(fun, closure) => match fun.get_args_n() {
+            Some(0) | None => fun.call(vec![], loc, contexes, closure),
+            Some(_) => VariableValue::Function(fun, closure),
+        },
+        x => x,
+    }
+}
+
+/// Returns the value of a branch in tail position, like `call_branch` does
+fn call_branch_tail<'a>(
+    value: VariableValue<'a>,
+    loc: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+    caller: &Vec<ContextRef<'a>>,
+) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+    match value {
+        VariableValue::Function(fun, closure) => match fun.get_args_n() {
+            Some(0) if fun.is_tail_callable(caller) => Err(Box::new(TailCall {
+                function: fun,
+                args: vec![],
+                named: vec![],
+                closure,
+                location: loc,
+            })),
+            Some(0) | None => fun.call_tail(vec![], vec![], loc, contexes, closure, caller),
+            Some(_) => Ok(VariableValue::Function(fun, closure)),
+        },
+        x => Ok(x),
+    }
+}
+
+/// What a loop does after an iteration, see `loop_flow`
+enum Flow<'a> {
+    /// Runs the next iteration; the value is that of the iteration
+    Next(VariableValue<'a>),
+    /// Stops the loop, which yields this value
+    Exit(VariableValue<'a>),
+}
+
+fn loop_flow<'a>(res: VariableValue<'a>, contexes: &[ContextRef<'a>]) -> Flow<'a> {
+    /*! Returns what a loop does after an iteration which yielded `res`, taking back the `#break()` or `#continue()` raised within it.
+    The loop stops if a `#return()` was raised, which is left for the function enclosing the loop to take.
+    An iteration yielding `[bail]`, like a trailing `#if()` whose condition doesn't hold, doesn't stop the loop: only `#loop()` does so.
+    */
+    match signal::take_loop(contexes) {
+        Some(Signal::Break(value, _)) => Flow::Exit(value),
+        Some(Signal::Continue(_)) => Flow::Next(VariableValue::Nil),
+        Some(Signal::Return(_)) => unreachable!("signal::take_loop leaves #return() raised"),
+        None if signal::is_raised(contexes) => Flow::Exit(res),
+        None => Flow::Next(res),
+    }
 }
 
 fn has_bailed(value: &VariableValue) -> bool {
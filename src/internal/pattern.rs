@@ -4,14 +4,15 @@ use std::fmt;
 pub struct IntPattern<T> {
     pub name: String,
     pub fun: T,
+    pub args_n: Option<usize>,
 }
 
 impl<'a, T> IntPattern<T>
 where
     T: Fn(Vec<VariableValue<'a>>, Location<'a>, &Vec<ContextRef<'a>>) -> VariableValue<'a>,
 {
-    pub fn new(name: String, fun: T) -> IntPattern<T> {
-        IntPattern { name, fun }
+    pub fn new(name: String, args_n: Option<usize>, fun: T) -> IntPattern<T> {
+        IntPattern { name, fun, args_n }
     }
 }
 
@@ -23,6 +24,10 @@ where
         self.name.clone()
     }
 
+    fn get_args_n(&self) -> Option<usize> {
+        self.args_n
+    }
+
     fn call_member(
         &self,
         args: Vec<VariableValue<'a>>,
@@ -43,3 +48,146 @@ where
         write!(f, "IntPattern({})", self.name)
     }
 }
+
+/** An internal method of a primitive type, like `'len` on strings (see `internal::methods`).
+    `fun` receives the value on which the method is called, followed by the arguments.
+**/
+pub struct IntMethod<T> {
+    pub name: String,
+    pub fun: T,
+    pub args_n: usize,
+}
+
+impl<'a, T> IntMethod<T>
+where
+    T: Fn(VariableValue<'a>, Vec<VariableValue<'a>>, Location<'a>, &Vec<ContextRef<'a>>) -> VariableValue<'a>,
+{
+    pub fn new(name: String, args_n: usize, fun: T) -> IntMethod<T> {
+        IntMethod { name, fun, args_n }
+    }
+}
+
+impl<'a, T> Callable<'a> for IntMethod<T>
+where
+    T: Fn(VariableValue<'a>, Vec<VariableValue<'a>>, Location<'a>, &Vec<ContextRef<'a>>) -> VariableValue<'a>,
+{
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_args_n(&self) -> Option<usize> {
+        Some(self.args_n)
+    }
+
+    fn call_member(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        _closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        if args.len() != self.args_n {
+            CompError::new(
+                203,
+                format!(
+                    "Mismatching number of arguments: expected {}, got {}.",
+                    self.args_n,
+                    args.len()
+                ),
+                CompLocation::from(location),
+            )
+            .print_and_exit();
+        }
+        (self.fun)(parent.unwrap(), args, location, contexes)
+    }
+}
+
+impl<'a, T> fmt::Debug for IntMethod<T>
+where
+    T: Fn(VariableValue<'a>, Vec<VariableValue<'a>>, Location<'a>, &Vec<ContextRef<'a>>) -> VariableValue<'a>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IntMethod({})", self.name)
+    }
+}
+
+/// The outcome of a branching internal pattern, like `#if`
+pub enum Branch<'a> {
+    /// The pattern yields this value
+    Value(VariableValue<'a>),
+    /// The pattern yields the value of this branch; see `call_branch`
+    Take(VariableValue<'a>),
+}
+
+/** An internal pattern which may yield the value of one of its arguments, like `#if`, `#else` and `#elseif`.
+    The branch taken is in tail position, letting it return tail calls.
+**/
+pub struct BranchPattern<T> {
+    pub name: String,
+    pub fun: T,
+    pub args_n: Option<usize>,
+}
+
+impl<'a, T> BranchPattern<T>
+where
+    T: Fn(Vec<VariableValue<'a>>, &Vec<ContextRef<'a>>) -> Branch<'a>,
+{
+    pub fn new(name: String, args_n: Option<usize>, fun: T) -> BranchPattern<T> {
+        BranchPattern { name, fun, args_n }
+    }
+}
+
+impl<'a, T> Callable<'a> for BranchPattern<T>
+where
+    T: Fn(Vec<VariableValue<'a>>, &Vec<ContextRef<'a>>) -> Branch<'a>,
+{
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_args_n(&self) -> Option<usize> {
+        self.args_n
+    }
+
+    fn call_member(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        _closure: Vec<(String, VariableValue<'a>)>,
+        _parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        match (self.fun)(args, contexes) {
+            Branch::Value(value) => value,
+            Branch::Take(branch) => call_branch(branch, location, contexes),
+        }
+    }
+
+    fn call_tail(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        caller: &Vec<ContextRef<'a>>,
+    ) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+        if !named.is_empty() {
+            return Ok(self.call_named(args, named, location, contexes, closure, None));
+        }
+        match (self.fun)(args, contexes) {
+            Branch::Value(value) => Ok(value),
+            Branch::Take(branch) => call_branch_tail(branch, location, contexes, caller),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for BranchPattern<T>
+where
+    T: Fn(Vec<VariableValue<'a>>, &Vec<ContextRef<'a>>) -> Branch<'a>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BranchPattern({})", self.name)
+    }
+}
@@ -0,0 +1,222 @@
+//! Runs destructuring definitions and tuple element assignments, for both the interpreter and the VM.
+
+use super::*;
+use token::Operator;
+
+/** Stores `value` in the tuple element or the destructuring target `member` of `expr`, returning the previous value(s).
+    Members are set through `instance::set_field` instead, as their object is evaluated before the value.
+**/
+pub fn define<'a>(
+    expr: &RExpression<'a>,
+    member: &RDefineMember,
+    value: VariableValue<'a>,
+    location: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    match member {
+        RDefineMember::Member(_) => unreachable!("Members are set through instance::set_field"),
+        RDefineMember::Index(index) => set_index(&expr.terms, *index, value, location, contexes),
+        RDefineMember::Tuple(target) => destructure(target, value, &location, &mut |var, value| {
+            with_variable(
+                var,
+                contexes,
+                |x| std::mem::replace(x, value),
+                location.clone(),
+            )
+        }),
+    }
+}
+
+/** Binds the elements of `value` to the variables of `target` through `set`, which returns their previous value.
+    The previous values are returned in the shape of `target`; errors out if `value` does not have that shape.
+**/
+pub fn destructure<'a>(
+    target: &RDestructure,
+    value: VariableValue<'a>,
+    location: &Location<'a>,
+    set: &mut dyn FnMut(&RSymRef, VariableValue<'a>) -> VariableValue<'a>,
+) -> VariableValue<'a> {
+    match (target, value) {
+        (RDestructure::Ignore, _) => VariableValue::Nil,
+        (RDestructure::Variable(var), value) => set(var, value),
+        (RDestructure::Tuple(targets), VariableValue::Tuple(values))
+            if targets.len() == values.len() =>
+        {
+            VariableValue::Tuple(
+                targets
+                    .iter()
+                    .zip(values)
+                    .map(|(target, value)| destructure(target, value, location, set))
+                    .collect(),
+            )
+        }
+        (RDestructure::Tuple(targets), value) => CompError::new(
+            213,
+            format!(
+                "Mismatching tuple length in destructuring: expected a tuple of {} elements, got {}",
+                targets.len(),
+                value
+            ),
+            CompLocation::from(location.clone()),
+        )
+        .print_and_exit(),
+    }
+}
+
+/** Runs the compound definition of `target`, ie. `target +: value`, whose value has already been evaluated, returning the previous value.
+    Members are updated through `compound_member` instead, as their object is evaluated before the value.
+**/
+pub fn compound<'a>(
+    target: &RDefineTarget<'a>,
+    op: Operator,
+    value: VariableValue<'a>,
+    location: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    let apply = apply(op, value, location.clone());
+    match target {
+        RDefineTarget::Variable(var) => with_variable(var, contexes, apply, location),
+        RDefineTarget::Index(expr, index) => {
+            let index = *index;
+            update(
+                &expr.terms,
+                &location,
+                contexes,
+                Box::new(|tuple| element(tuple, index, &location, apply)),
+            )
+        }
+        RDefineTarget::Member(_, _) => unreachable!("Members are updated through compound_member"),
+    }
+}
+
+/** Runs the compound definition of the field `name` of `object`, which has already been evaluated, returning its previous value **/
+pub fn compound_member<'a>(
+    object: VariableValue<'a>,
+    name: &str,
+    op: Operator,
+    value: VariableValue<'a>,
+    location: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    let apply = apply(op, value, location.clone());
+    update_field(object, name, &location, contexes, apply)
+}
+
+fn apply<'a>(
+    op: Operator,
+    value: VariableValue<'a>,
+    location: Location<'a>,
+) -> impl FnOnce(&mut VariableValue<'a>) -> VariableValue<'a> {
+    //! Returns the update made by a compound definition, which returns the previous value
+    move |x| {
+        let old = x.clone();
+        // like in `execute_op`, the right-hand side is the receiver
+        *x = value.binary_op(old.clone(), &op, location);
+        old
+    }
+}
+
+fn set_index<'a>(
+    terms: &[RExprTerm<'a>],
+    index: usize,
+    value: VariableValue<'a>,
+    location: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    //! Replaces the `index`-th element of the tuple stored in `terms`, returning its previous value
+    update(
+        terms,
+        &location,
+        contexes,
+        Box::new(|tuple| element(tuple, index, &location, |x| std::mem::replace(x, value))),
+    )
+}
+
+fn element<'a>(
+    tuple: &mut VariableValue<'a>,
+    index: usize,
+    location: &Location<'a>,
+    f: impl FnOnce(&mut VariableValue<'a>) -> VariableValue<'a>,
+) -> VariableValue<'a> {
+    //! Applies `f` to the `index`-th element of `tuple`, erroring out if there is none
+    match tuple {
+        VariableValue::Tuple(values) if index < values.len() => f(&mut values[index]),
+        VariableValue::Tuple(values) => CompError::new(
+            205,
+            format!(
+                "Invalid tuple member accessor: cannot assign to element {} of a tuple of {} elements",
+                index,
+                values.len()
+            ),
+            CompLocation::from(location),
+        )
+        .print_and_exit(),
+        x => CompError::new(
+            205,
+            format!("Invalid tuple member accessor: cannot assign to element {} of {}", index, x),
+            CompLocation::from(location),
+        )
+        .print_and_exit(),
+    }
+}
+
+fn update<'a, 'b>(
+    terms: &[RExprTerm<'a>],
+    location: &Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+    f: Box<dyn FnOnce(&mut VariableValue<'a>) -> VariableValue<'a> + 'b>,
+) -> VariableValue<'a> {
+    /*! Applies `f` to the variable, field or tuple element described by `terms`, returning its result; see `resolve::is_assignable`.
+    The object holding a field is only evaluated once, even if an element of that field is updated.
+    */
+    match terms {
+        [RExprTerm::Push(RASTNode::Variable(var))] => {
+            with_variable(var, contexes, f, location.clone())
+        }
+        [prefix @ .., RExprTerm::Push(RASTNode::Member(name)), RExprTerm::Op(Operator::MemberAccessor)] => {
+            let object = interprete_expression(
+                &RExpression {
+                    terms: prefix.to_vec(),
+                    max_depth: prefix.len(),
+                },
+                location.clone(),
+                contexes,
+            );
+            update_field(object, name, location, contexes, f)
+        }
+        [prefix @ .., RExprTerm::Push(index), RExprTerm::Op(Operator::MemberAccessor)] => {
+            let index = tuple_index(index).expect("Assigned expressions are checked by resolve::is_assignable");
+            update(
+                prefix,
+                location,
+                contexes,
+                Box::new(move |tuple| element(tuple, index, location, f)),
+            )
+        }
+        _ => unreachable!("Assigned expressions are checked by resolve::is_assignable"),
+    }
+}
+
+fn update_field<'a>(
+    object: VariableValue<'a>,
+    name: &str,
+    location: &Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+    f: impl FnOnce(&mut VariableValue<'a>) -> VariableValue<'a>,
+) -> VariableValue<'a> {
+    //! Applies `f` to the field `name` of `object`, or to its static member if it is a type, returning its result
+    let (t, vars) = match object {
+        VariableValue::Instance(t, vars) | VariableValue::Super(_, t, vars) => (t, vars),
+        VariableValue::Type(t) => {
+            let mut member = instance::get_static(&t, name, location.clone(), contexes);
+            let res = f(&mut member);
+            instance::set_static(&t, name, member, location.clone(), contexes);
+            return res;
+        }
+        _ => panic!("Trying to set value on non-object"),
+    };
+    let mut field = instance::get_field(&t, &vars, name, location.clone());
+    let res = f(&mut field);
+    instance::set_field(&t, &vars, name, field, location.clone());
+    res
+}
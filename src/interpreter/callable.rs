@@ -1,8 +1,58 @@
 use super::*;
-use std::cell::RefCell;
-use std::collections::HashMap;
+use crate::vm;
+use crate::parser::token::TypeStrictness;
+use std::cell::{Ref, RefCell};
 use std::fmt;
 
+/// The maximum call depth if none is given with `--max-depth`
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 4096;
+
+thread_local!(pub static MAX_CALL_DEPTH: RefCell<usize> = const { RefCell::new(DEFAULT_MAX_CALL_DEPTH) });
+thread_local!(static CALL_DEPTH: RefCell<usize> = const { RefCell::new(0) });
+
+/** A call in tail position, which is returned instead of being made.
+    The innermost function call that isn't itself in tail position (see `RFunction::call_named`) then makes it in place of the function that returned it, letting tail-recursive functions run in constant stack space.
+**/
+pub struct TailCall<'a> {
+    pub function: RPatRef<'a>,
+    pub args: Vec<VariableValue<'a>>,
+    pub named: Vec<(String, VariableValue<'a>)>,
+    pub closure: Vec<(String, VariableValue<'a>)>,
+    pub location: Location<'a>,
+}
+
+/** Counts a call on the call stack for as long as it lives.
+    Entering a call past `MAX_CALL_DEPTH` errors out with a stack overflow, rather than letting the interpreter overflow its native stack.
+**/
+struct CallDepth;
+
+impl CallDepth {
+    fn enter(location: &Location<'_>) -> CallDepth {
+        let max = MAX_CALL_DEPTH.with(|max| *max.borrow());
+        let depth = CALL_DEPTH.with(|depth| *depth.borrow());
+        if depth >= max {
+            CompError::new(
+                208,
+                format!("Stack overflow: exceeded the maximum call depth of {}", max),
+                location.clone().into(),
+            )
+            .append(
+                "The maximum call depth can be raised with --max-depth".to_string(),
+                CompLocation::None,
+            )
+            .print_and_exit();
+        }
+        CALL_DEPTH.with(|depth| *depth.borrow_mut() += 1);
+        CallDepth
+    }
+}
+
+impl Drop for CallDepth {
+    fn drop(&mut self) {
+        CALL_DEPTH.with(|depth| *depth.borrow_mut() -= 1);
+    }
+}
+
 pub trait Callable<'a> {
     fn get_name(&self) -> String;
 
@@ -16,9 +66,60 @@ pub trait Callable<'a> {
     ) -> VariableValue<'a>;
 
     fn get_args_n(&self) -> Option<usize> {
+        //! Returns the arity of the function: the number of arguments which a call must give, or None if it varies
+        None
+    }
+
+    fn takes_args_n(&self, n: usize) -> bool {
+        //! Returns wether or not one may call the function with exactly `n` arguments, as interfaces require of their patterns
+        self.get_args_n() == Some(n)
+    }
+
+    fn get_location(&self) -> Option<Location<'a>> {
+        None
+    }
+
+    fn get_arg_names(&self) -> Option<Vec<String>> {
         None
     }
 
+    fn get_doc(&self) -> Option<String> {
+        //! Returns the doc comment (`///`) preceding the function's declaration, if any
+        None
+    }
+
+    fn catches_return(&self) -> bool {
+        /*! Returns wether or not `#return()` exits this function.
+        Named functions (patterns, methods and functions bound to a variable) do, while anonymous ones, like the blocks given to `#if` or `#for`, let it through to the function enclosing them.
+        */
+        false
+    }
+
+    fn call_named(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        //! Calls the function with both positional and named arguments; errors out if named arguments are given to a function which doesn't support them
+        if let Some((name, _)) = named.first() {
+            CompError::new(
+                207,
+                format!(
+                    "Unknown named argument {}: {} does not take named arguments",
+                    name,
+                    self.get_name()
+                ),
+                location.into(),
+            )
+            .print_and_exit();
+        }
+        self.call_member(args, location, contexes, closure, parent)
+    }
+
     fn call(
         &self,
         args: Vec<VariableValue<'a>>,
@@ -28,6 +129,26 @@ pub trait Callable<'a> {
     ) -> VariableValue<'a> {
         self.call_member(args, location, contexes, closure, None)
     }
+
+    fn is_tail_callable(&self, _contexes: &Vec<ContextRef<'a>>) -> bool {
+        //! Returns wether or not a call to this function in tail position may be made from the contexes `contexes` instead
+        false
+    }
+
+    fn call_tail(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        _caller: &Vec<ContextRef<'a>>,
+    ) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+        /*! Calls the function from tail position; calls in tail position within it may be returned as a `TailCall` rather than be made.
+        @param caller - The contexes of the innermost call not in tail position, from which the returned `TailCall` will be made
+        */
+        Ok(self.call_named(args, named, location, contexes, closure, None))
+    }
 }
 
 impl<'a> fmt::Debug for dyn Callable<'a> + 'a {
@@ -41,6 +162,35 @@ impl<'a> Callable<'a> for RPattern<'a> {
         self.name.clone()
     }
 
+    fn get_args_n(&self) -> Option<usize> {
+        //! Overloads taking different numbers of arguments have no single number of arguments
+        let overloads = self.overloads.borrow();
+        let args_n = overloads.first()?.get_args_n();
+        overloads.iter().all(|f| f.get_args_n() == args_n).then_some(args_n).flatten()
+    }
+
+    fn takes_args_n(&self, n: usize) -> bool {
+        self.overloads.borrow().iter().any(|f| f.takes_args_n(n))
+    }
+
+    fn get_location(&self) -> Option<Location<'a>> {
+        self.overloads.borrow().first().and_then(|f| f.get_location())
+    }
+
+    fn get_arg_names(&self) -> Option<Vec<String>> {
+        let overloads = self.overloads.borrow();
+        let names = overloads.first()?.get_arg_names();
+        overloads.iter().all(|f| f.get_arg_names() == names).then_some(names).flatten()
+    }
+
+    fn get_doc(&self) -> Option<String> {
+        self.overloads.borrow().iter().find_map(|f| f.get_doc())
+    }
+
+    fn catches_return(&self) -> bool {
+        true
+    }
+
     fn call_member(
         &self,
         args: Vec<VariableValue<'a>>,
@@ -49,17 +199,161 @@ impl<'a> Callable<'a> for RPattern<'a> {
         closure: Vec<(String, VariableValue<'a>)>,
         parent: Option<VariableValue<'a>>,
     ) -> VariableValue<'a> {
-        self.function
-            .borrow()
-            .as_ref()
-            .unwrap()
-            .call_member(args, location, contexes, closure, parent)
+        self.call_named(args, vec![], location, contexes, closure, parent)
+    }
+
+    fn call_named(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        self.dispatch(&args, &named, &location)
+            .call_named(args, named, location, contexes, closure, parent)
+    }
+
+    fn is_tail_callable(&self, contexes: &Vec<ContextRef<'a>>) -> bool {
+        // the overload isn't known until the call is made, so all of them must be
+        let overloads = self.overloads.borrow();
+        !overloads.is_empty() && overloads.iter().all(|f| f.is_tail_callable(contexes))
+    }
+
+    fn call_tail(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        caller: &Vec<ContextRef<'a>>,
+    ) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+        self.dispatch(&args, &named, &location)
+            .call_tail(args, named, location, contexes, closure, caller)
+    }
+}
+
+impl<'a> RPattern<'a> {
+    fn dispatch(
+        &self,
+        args: &[VariableValue<'a>],
+        named: &[(String, VariableValue<'a>)],
+        location: &Location<'a>,
+    ) -> Ref<'_, RFunction<'a>> {
+        /*! Returns the overload to call with `args` and `named`: the one accepting them which is more specific than all of the others accepting them (see `more_specific`).
+        A pattern declared only once is always called, so that its arguments are checked as usual.
+        */
+        let overloads = self.overloads.borrow();
+        let index = if overloads.len() == 1 {
+            0
+        } else {
+            let accepting: Vec<usize> = (0..overloads.len())
+                .filter(|index| accepts(&overloads[*index], args, named))
+                .collect();
+            let best: Vec<usize> = accepting
+                .iter()
+                .copied()
+                .filter(|f| {
+                    accepting.iter().all(|g| {
+                        f == g
+                            || (more_specific(&overloads[*f], &overloads[*g], args.len())
+                                && !more_specific(&overloads[*g], &overloads[*f], args.len()))
+                    })
+                })
+                .collect();
+            match best[..] {
+                [index] => index,
+                _ => {
+                    let values = args
+                        .iter()
+                        .map(|arg| format!("{}", arg))
+                        .chain(named.iter().map(|(name, arg)| format!("{}: {}", name, arg)))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    let (msg, candidates) = if accepting.is_empty() {
+                        (format!("No declaration of {} accepts the arguments ({})", self.name, values), (0..overloads.len()).collect())
+                    } else {
+                        (format!("Ambiguous call to {}: several declarations accept the arguments ({})", self.name, values), accepting)
+                    };
+                    let mut error = CompError::new(217, msg, location.into());
+                    for index in candidates {
+                        error = error.append(
+                            format!("{} is declared here", self.name),
+                            CompLocation::from(&overloads[index].location),
+                        );
+                    }
+                    error.print_and_exit()
+                }
+            }
+        };
+        Ref::map(overloads, |overloads| &overloads[index])
     }
 }
 
+fn accepts<'a>(function: &RFunction<'a>, args: &[VariableValue<'a>], named: &[(String, VariableValue<'a>)]) -> bool {
+    //! Returns wether or not `function` may be called with `args` and `named`, as to their number and their types
+    function.accepts_args_n(args.len() + named.len())
+        && args
+            .iter()
+            .zip(function.args.iter())
+            .all(|(value, arg)| accepts_argtype(arg, value))
+        && named.iter().all(|(name, value)| {
+            function.args.iter().any(|arg| arg.name == *name && accepts_argtype(arg, value))
+        })
+}
+
+fn more_specific<'a>(f: &RFunction<'a>, g: &RFunction<'a>, n: usize) -> bool {
+    //! Returns wether or not each of the first `n` arguments of `f` is typed at least as precisely as that of `g`: untyped, or with a type conforming to that of `g`
+    (0..n).all(|index| {
+        let argtype = |function: &RFunction<'a>| function.args.get(index).and_then(|arg| arg.argtype.upgrade());
+        match (argtype(f), argtype(g)) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(f_type), Some(g_type)) => f_type.borrow().conforms_to(&g_type),
+        }
+    })
+}
+
 impl<'a> Callable<'a> for RFunction<'a> {
     fn get_name(&self) -> String {
-        "<anonymous function>".to_string()
+        self.name
+            .clone()
+            .unwrap_or_else(|| "<anonymous function>".to_string())
+    }
+
+    fn get_args_n(&self) -> Option<usize> {
+        //! Arguments with a default value may be left out, so only the other ones count; a #rest() argument takes any number of them
+        if self.rest.is_some() {
+            return None;
+        }
+        Some(self.required_args_n())
+    }
+
+    fn takes_args_n(&self, n: usize) -> bool {
+        self.accepts_args_n(n)
+    }
+
+    fn get_location(&self) -> Option<Location<'a>> {
+        Some(self.location.clone())
+    }
+
+    fn get_arg_names(&self) -> Option<Vec<String>> {
+        Some(self.args.iter().map(|arg| arg.name.clone()).collect())
+    }
+
+    fn get_doc(&self) -> Option<String> {
+        self.doc.clone()
+    }
+
+    fn catches_return(&self) -> bool {
+        self.name.is_some()
+    }
+
+    fn is_tail_callable(&self, contexes: &Vec<ContextRef<'a>>) -> bool {
+        // #lhs(), #self() and #super() depend on the call site, which a tail call replaces
+        !self.has_lhs && !self.has_self && !self.has_new && !self.has_super && self.in_scope(contexes)
     }
 
     fn call_member(
@@ -70,15 +364,146 @@ impl<'a> Callable<'a> for RFunction<'a> {
         closure: Vec<(String, VariableValue<'a>)>,
         parent: Option<VariableValue<'a>>,
     ) -> VariableValue<'a> {
-        //! Asserts that contexes is not empty
-        if let Some((depth, ulid, ref_loc)) = self.required_ctx.clone() {
-            let mut ctx_found = false;
-            for ctx in contexes.iter() {
-                if ctx.borrow().depth == depth && ctx.borrow().ulid == ulid {
-                    ctx_found = true;
+        self.call_named(args, vec![], location, contexes, closure, parent)
+    }
+
+    fn call_named(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        //! Calls the function, then makes the tail calls it returns in its place, until a value is yielded
+        let _depth = CallDepth::enter(&location);
+
+        if self.has_new {
+            if let Some(VariableValue::Type(type_raw)) = parent {
+                let instance = instance::instantiate(type_raw, vec![], location.clone(), contexes);
+                let frame = self.bind(args, named, location, contexes, closure, Some(instance.clone()));
+                let _ = self.run(frame, None);
+                signal::take_return(contexes);
+
+                return instance;
+            } else {
+                unimplemented!("Non-struct #new() is not supported yet!");
+            }
+        }
+        // the overload called on a type is only known once dispatched, so this is checked here rather than by the caller
+        if let Some(VariableValue::Type(t)) = &parent {
+            if self.takes_self() {
+                CompError::new(
+                    210,
+                    format!(
+                        "Cannot call method {} on type {}: it takes #self(), so it must be called on an instance",
+                        self.get_name(),
+                        t.borrow().name
+                    ),
+                    CompLocation::from(&location),
+                )
+                .print_and_exit();
+            }
+        }
+
+        let frame = self.bind(args, named, location, contexes, closure, parent);
+        let mut res = self.run(frame, Some(contexes));
+        // the functions called in tail position share this call's value, and thus the `#return()`s within them
+        let mut catches_return = self.catches_return();
+        loop {
+            match res {
+                Ok(value) if catches_return => return signal::take_return(contexes).unwrap_or(value),
+                Ok(value) => return value,
+                Err(tail) => {
+                    catches_return |= tail.function.catches_return();
+                    res = tail.function.call_tail(
+                        tail.args,
+                        tail.named,
+                        tail.location,
+                        contexes,
+                        tail.closure,
+                        contexes,
+                    )
                 }
             }
-            if !ctx_found {
+        }
+    }
+
+    fn call_tail(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        caller: &Vec<ContextRef<'a>>,
+    ) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+        if self.has_new {
+            return Ok(self.call_named(args, named, location, contexes, closure, None));
+        }
+        let _depth = CallDepth::enter(&location);
+
+        let frame = self.bind(args, named, location, contexes, closure, None);
+        match self.run(frame, Some(caller)) {
+            Ok(value) if self.catches_return() => Ok(signal::take_return(contexes).unwrap_or(value)),
+            res => res,
+        }
+    }
+}
+
+impl<'a> RFunction<'a> {
+    fn in_scope(&self, contexes: &Vec<ContextRef<'a>>) -> bool {
+        //! Returns wether or not the context that the function references (if any) is within `contexes`
+        match &self.required_ctx {
+            Some((depth, ulid, _)) => contexes
+                .iter()
+                .any(|ctx| ctx.borrow().depth == *depth && ctx.borrow().ulid == *ulid),
+            None => true,
+        }
+    }
+
+    fn run(
+        &self,
+        frame: Vec<ContextRef<'a>>,
+        caller: Option<&Vec<ContextRef<'a>>>,
+    ) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+        /*! Runs the body of the function within `frame`, as returned by `bind`, using the bytecode VM if it is active.
+        @param caller - The contexes to which tail calls may be returned (see `interprete_tail`); they are made right away if `None`
+        */
+        if vm::is_active() {
+            vm::run_function(self, frame, caller)
+        } else {
+            match caller {
+                Some(caller) => interprete_tail(self.block(), frame, caller),
+                None => Ok(interprete(self.block(), frame)),
+            }
+        }
+    }
+
+    pub fn block(&self) -> RASTRef<'a> {
+        //! Returns the body of the function, which is the last instruction of its `init` RAST
+        match self.body.borrow().instructions.last().unwrap() {
+            (RASTNode::Block(body), _) => body.clone(),
+            _ => panic!("Expected function body node to be a block"),
+        }
+    }
+
+    /** Binds the arguments of a call to the function, returning `contexes` with the function's context pushed onto it.
+        @param parent - The value of `self` within the function, if it has #self() or #new()
+    **/
+    fn bind(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
+    ) -> Vec<ContextRef<'a>> {
+        //! Asserts that contexes is not empty
+        if let Some((_, _, ref_loc)) = self.required_ctx.clone() {
+            if !self.in_scope(contexes) {
                 CompError::new(
                     206,
                     format!("Function fell out of scope"),
@@ -92,77 +517,211 @@ impl<'a> Callable<'a> for RFunction<'a> {
 
         let mut init_ctx = Context::from(self.body.clone());
 
-        if args.len() != self.args.len() {
+        let required_n = self.required_args_n();
+        let given_n = args.len() + named.len();
+        if !self.accepts_args_n(given_n) {
             CompError::new(
                 203,
                 format!(
                     "Mismatching number of arguments: expected {}, got {}.",
-                    self.args.len(),
-                    args.len()
+                    expected_args_n(self, required_n),
+                    given_n
                 ),
                 CompLocation::from(location),
             )
             .print_and_exit();
         }
 
-        for (from, to) in args.into_iter().zip(self.args.iter()) {
-            // TODO: conversion
-            init_ctx.variables.insert(to.name.clone(), from);
+        let mut args = args.into_iter().map(VariableValue::into_instance);
+        let mut values: Vec<Option<VariableValue<'a>>> =
+            self.args.iter().map(|_| args.next()).collect();
+
+        for (name, value) in named.into_iter() {
+            match self.args.iter().position(|arg| arg.name == name) {
+                Some(index) if values[index].is_none() => values[index] = Some(value.into_instance()),
+                Some(_) => CompError::new(
+                    207,
+                    format!("Duplicate named argument {}: it is already given", name),
+                    location.into(),
+                )
+                .print_and_exit(),
+                None => CompError::new(
+                    207,
+                    format!("Unknown named argument {} in call to {}", name, self.get_name()),
+                    location.into(),
+                )
+                .print_and_exit(),
+            }
+        }
+
+        let mut missing = Vec::<usize>::new();
+        for (index, (arg, value)) in self.args.iter().zip(values).enumerate() {
+            match value {
+                // TODO: conversion
+                Some(value) => {
+                    check_argtype(self, arg, &value, &location);
+                    if let Some(pattern) = &arg.pattern {
+                        assign::destructure(pattern, value.clone(), &location, &mut |var, value| {
+                            init_ctx.set(var.slot, value);
+                            VariableValue::Nil
+                        });
+                    }
+                    init_ctx.set(index, value)
+                }
+                None if arg.default.is_some() => missing.push(index),
+                None => CompError::new(
+                    203,
+                    format!("Missing argument {} in call to {}", arg.name, self.get_name()),
+                    location.into(),
+                )
+                .print_and_exit(),
+            }
+        }
+
+        if self.rest.is_some() {
+            init_ctx.set(self.rest_slot(), VariableValue::Tuple(args.collect()));
         }
 
         for (name, value) in closure.into_iter() {
-            init_ctx.variables.insert(name, value);
+            // values captured by something else than this function have no slot, and are left out
+            if let Some(index) = self.closure.iter().position(|(var, _)| *var == name) {
+                init_ctx.set(self.closure_slot(index), value);
+            }
         }
 
         if self.has_lhs {
-            init_ctx.variables.insert(
-                "lhs".to_string(),
+            init_ctx.set(
+                self.lhs_slot(),
                 contexes.last().unwrap().borrow().last_value.clone(),
             );
         }
-        if self.has_new {
-            if let Some(VariableValue::Type(type_raw)) = parent {
-                let obj: InstanceRef<'_> = Rc::new(RefCell::new(HashMap::new()));
-                init_ctx.variables.insert(
-                    "self".to_string(),
-                    VariableValue::Instance(type_raw.clone(), obj.clone()),
-                );
-
-                let mut contexes = contexes.clone();
-                contexes.push(Rc::new(RefCell::new(init_ctx)));
-
-                match self.body.borrow().instructions.last().unwrap() {
-                    (RASTNode::Block(body), _) => interprete(body.clone(), contexes),
-                    _ => panic!("Expected function body node to be a block"),
-                };
+        if self.has_super {
+            // `super` is `self`, whose methods are looked up from the parent of the struct declaring the method;
+            // `self` stays an instance of its own struct, so that the methods of the parent still call its overrides
+            let value = match (&parent, self.body.borrow().parent.upgrade()) {
+                (Some(VariableValue::Instance(t, vars)), Some(context)) => RStruct::super_of(t, &context)
+                    .map(|st| VariableValue::Super(st, t.clone(), vars.clone())),
+                _ => None,
+            };
+            init_ctx.set(self.super_slot(), value.unwrap_or(VariableValue::Nil));
+        }
+        // TODO: has_self
+        if self.has_self || self.has_new {
+            init_ctx.set(self.self_slot(), parent.unwrap());
+        }
 
-                VariableValue::Instance(type_raw, obj)
-            } else {
-                unimplemented!("Non-struct #new() is not supported yet!");
-            }
-        } else {
-            // TODO: has_self
+        let mut contexes = contexes.clone();
+        contexes.push(Rc::new(RefCell::new(init_ctx)));
+        self.bind_defaults(missing, &contexes);
+
+        contexes
+    }
 
-            if self.has_self {
-                init_ctx
-                    .variables
-                    .insert("self".to_string(), parent.unwrap());
+    /** Evaluates the default values of the arguments which weren't given, in order.
+        Each default value is evaluated within the function's own context (the last one of `contexes`), so that it may refer to the preceding arguments.
+    **/
+    fn bind_defaults(&self, missing: Vec<usize>, contexes: &Vec<ContextRef<'a>>) {
+        for index in missing {
+            let arg = &self.args[index];
+            if let Some(default) = &arg.default {
+                let value = if vm::is_active() {
+                    vm::default_value(self, index, contexes)
+                } else {
+                    interprete(default.clone(), contexes.clone())
+                };
+                contexes
+                    .last()
+                    .unwrap()
+                    .borrow_mut()
+                    .set(index, value);
             }
+        }
+    }
+}
 
-            let mut contexes = contexes.clone();
-            contexes.push(Rc::new(RefCell::new(init_ctx)));
+/// Asserts that `value` may be given as `arg`, see `accepts_argtype`
+fn check_argtype<'a>(
+    function: &RFunction<'a>,
+    arg: &RFunctionArg<'a>,
+    value: &VariableValue<'a>,
+    location: &Location<'a>,
+) {
+    if accepts_argtype(arg, value) {
+        return;
+    }
+    let argtype = arg.argtype.upgrade().unwrap();
+    CompError::new(
+        211,
+        format!(
+            "Mismatching argument type: expected an instance of {} for argument {} of {}, got {}",
+            argtype.borrow().name,
+            arg.name,
+            function.get_name(),
+            value
+        ),
+        location.into(),
+    )
+    .print_and_exit();
+}
 
-            match self.body.borrow().instructions.last().unwrap() {
-                (RASTNode::Block(body), _) => interprete(body.clone(), contexes),
-                _ => panic!("Expected function body node to be a block"),
+/** Returns wether or not `value` may be given as `arg`, if it has a type: `<T>` accepts instances of T, of the structs extending it and of the structs implementing the interface T.
+    `<!T>` only accepts instances of T itself, while `<~T>` also accepts instances of structs having T's members.
+**/
+fn accepts_argtype<'a>(arg: &RFunctionArg<'a>, value: &VariableValue<'a>) -> bool {
+    let Some(argtype) = arg.argtype.upgrade() else {
+        return true;
+    };
+    match value {
+        // `super` is `self`, whose struct is that of the instance
+        VariableValue::Instance(st, _) | VariableValue::Super(_, st, _) => match arg.strictness {
+            TypeStrictness::Strict => Rc::ptr_eq(st, &argtype),
+            TypeStrictness::Normal => st.borrow().conforms_to(&argtype),
+            TypeStrictness::Loose => {
+                st.borrow().conforms_to(&argtype) || st.borrow().is_subtype_of(argtype.clone())
             }
-        }
+        },
+        _ => false,
+    }
+}
+
+/// Formats the number of arguments that `function` accepts, for error messages
+fn expected_args_n(function: &RFunction<'_>, required_n: usize) -> String {
+    if function.rest.is_some() {
+        format!("at least {}", required_n)
+    } else if required_n < function.args.len() {
+        format!("{} to {}", required_n, function.args.len())
+    } else {
+        format!("{}", required_n)
     }
 }
 
 impl<'a> Callable<'a> for RefCell<RFunction<'a>> {
     fn get_name(&self) -> String {
-        "<anonymous function>".to_string()
+        self.borrow().get_name()
+    }
+
+    fn get_args_n(&self) -> Option<usize> {
+        self.borrow().get_args_n()
+    }
+
+    fn takes_args_n(&self, n: usize) -> bool {
+        self.borrow().takes_args_n(n)
+    }
+
+    fn get_location(&self) -> Option<Location<'a>> {
+        self.borrow().get_location()
+    }
+
+    fn get_arg_names(&self) -> Option<Vec<String>> {
+        self.borrow().get_arg_names()
+    }
+
+    fn get_doc(&self) -> Option<String> {
+        self.borrow().get_doc()
+    }
+
+    fn catches_return(&self) -> bool {
+        self.borrow().catches_return()
     }
 
     fn call_member(
@@ -176,4 +735,34 @@ impl<'a> Callable<'a> for RefCell<RFunction<'a>> {
         self.borrow()
             .call_member(args, location, contexes, closure, parent)
     }
+
+    fn call_named(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        self.borrow()
+            .call_named(args, named, location, contexes, closure, parent)
+    }
+
+    fn is_tail_callable(&self, contexes: &Vec<ContextRef<'a>>) -> bool {
+        self.borrow().is_tail_callable(contexes)
+    }
+
+    fn call_tail(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        caller: &Vec<ContextRef<'a>>,
+    ) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+        self.borrow()
+            .call_tail(args, named, location, contexes, closure, caller)
+    }
 }
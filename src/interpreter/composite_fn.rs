@@ -29,36 +29,68 @@ impl<'a> Callable<'a> for CompositeFunction<'a> {
         format!("[{} {} {}]", self.left.0.get_name(), self.op, self.right.0.get_name())
     }
 
+    fn get_args_n(&self) -> Option<usize> {
+        if let Operator::Compose = self.op {
+            // only the first function receives the arguments
+            return self.left.0.get_args_n();
+        }
+        // both functions receive the same arguments
+        match (self.left.0.get_args_n(), self.right.0.get_args_n()) {
+            (Some(left), Some(right)) if left == right => Some(left),
+            (Some(n), None) | (None, Some(n)) => Some(n),
+            _ => None,
+        }
+    }
+
     fn call_member(
         &self,
         args: Vec<VariableValue<'a>>,
         location: Location<'a>,
         contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        self.call_named(args, vec![], location, contexes, closure, parent)
+    }
+
+    fn call_named(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
         _closure: Vec<(String, VariableValue<'a>)>,
         parent: Option<VariableValue<'a>>,
     ) -> VariableValue<'a> {
         match self.op {
+            Operator::Compose => {
+                let left = self.left.0.call_named(args, named, location.clone(), contexes, self.left.1.clone(), parent.clone());
+                if signal::is_raised(contexes) {
+                    return VariableValue::Nil;
+                }
+                self.right.0.call_named(vec![left], vec![], location, contexes, self.right.1.clone(), parent)
+            }
             Operator::And => {
-                let left = self.left.0.call_member(args.clone(), location.clone(), contexes, self.left.1.clone(), parent.clone());
+                let left = self.left.0.call_named(args.clone(), named.clone(), location.clone(), contexes, self.left.1.clone(), parent.clone());
                 if is_truthy(&left) {
-                    let right = self.right.0.call_member(args, location.clone(), contexes, self.right.1.clone(), parent);
+                    let right = self.right.0.call_named(args, named, location.clone(), contexes, self.right.1.clone(), parent);
                     left.binary_op(right, &self.op, location)
                 } else {
                     left
                 }
             }
             Operator::Or => {
-                let left = self.left.0.call_member(args.clone(), location.clone(), contexes, self.left.1.clone(), parent.clone());
+                let left = self.left.0.call_named(args.clone(), named.clone(), location.clone(), contexes, self.left.1.clone(), parent.clone());
                 if !is_truthy(&left) {
-                    let right = self.right.0.call_member(args, location.clone(), contexes, self.right.1.clone(), parent);
+                    let right = self.right.0.call_named(args, named, location.clone(), contexes, self.right.1.clone(), parent);
                     left.binary_op(right, &self.op, location)
                 } else {
                     left
                 }
             }
             _ => {
-                let left = self.left.0.call_member(args.clone(), location.clone(), contexes, self.left.1.clone(), parent.clone());
-                let right = self.right.0.call_member(args, location.clone(), contexes, self.right.1.clone(), parent);
+                let left = self.left.0.call_named(args.clone(), named.clone(), location.clone(), contexes, self.left.1.clone(), parent.clone());
+                let right = self.right.0.call_named(args, named, location.clone(), contexes, self.right.1.clone(), parent);
                 left.binary_op(right, &self.op, location)
             }
         }
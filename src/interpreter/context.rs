@@ -1,31 +1,48 @@
 use super::*;
 
 use std::cell::RefCell;
-use std::collections::HashMap;
 
 pub type ContextRef<'a> = Rc<RefCell<Context<'a>>>;
 
 #[derive(Debug)]
 pub struct Context<'a> {
     pub depth: usize,
-    pub variables: HashMap<String, VariableValue<'a>>,
+    /// The values of the variables, indexed by the `slot` of their `RSymRef`s
+    pub variables: Vec<VariableValue<'a>>,
     pub last_value: VariableValue<'a>,
     pub ulid: u128,
+    /// The control flow signal being raised, only ever set on the root context; see `signal`
+    pub signal: Option<Signal<'a>>,
 }
 
-impl<'a> From<RASTRef<'a>> for Context<'a> {
-    fn from(ast: RASTRef<'a>) -> Context<'a> {
-        let mut variables = HashMap::with_capacity(ast.borrow().variables.len());
-
-        for var in &ast.borrow().variables {
-            variables.insert(var.borrow().name.clone(), VariableValue::Nil);
-        }
-
+impl<'a> Context<'a> {
+    pub fn new(depth: usize, ulid: u128, size: usize) -> Context<'a> {
+        //! Creates a context holding `size` variables, all initialized to `Nil`
         Context {
-            depth: ast.borrow().depth,
-            variables,
+            depth,
+            variables: vec![VariableValue::Nil; size],
             last_value: VariableValue::Nil,
-            ulid: ast.borrow().ulid,
+            ulid,
+            signal: None,
         }
     }
+
+    pub fn set(&mut self, slot: usize, value: VariableValue<'a>) {
+        //! Sets the variable at `slot` to `value`; slots are resolved beforehand, so one out of the context's range is a bug
+        match self.variables.get_mut(slot) {
+            Some(var) => *var = value,
+            None => panic!(
+                "Slot {} is out of range of a context of {} variables",
+                slot,
+                self.variables.len()
+            ),
+        }
+    }
+}
+
+impl<'a> From<RASTRef<'a>> for Context<'a> {
+    fn from(ast: RASTRef<'a>) -> Context<'a> {
+        let ast = ast.borrow();
+        Context::new(ast.depth, ast.ulid, ast.variables.len())
+    }
 }
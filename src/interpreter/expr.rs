@@ -12,8 +12,9 @@ pub trait UnaryOp<'a> {
 pub enum ExprValue<'a> {
     Value(VariableValue<'a>),
     Member(String),
-    MethodCall(String, RASTRef<'a>),
+    MethodCall(String, Vec<VariableValue<'a>>, Vec<(String, VariableValue<'a>)>, PrimitiveMethodsRef<'a>),
     PartialTuple(Vec<PartialValue<'a>>),
+    Args(Vec<VariableValue<'a>>, Vec<(String, VariableValue<'a>)>),
 }
 
 #[derive(Debug)]
@@ -68,235 +69,348 @@ pub fn interprete_expression_int<'a>(
                         }
                     }).collect())
                 }
-                RASTNode::MethodCall(name, body) => {
-                    ExprValue::MethodCall(name.clone(), body.clone())
+                RASTNode::Tuple(vec, false)
+                    if vec.iter().any(|(x, _)| matches!(x, RASTNode::NamedArg(_, _))) =>
+                {
+                    let mut args: Vec<VariableValue<'a>> = Vec::new();
+                    let mut named: Vec<(String, VariableValue<'a>)> = Vec::new();
+                    for instruction in vec {
+                        match instruction {
+                            (RASTNode::NamedArg(name, value), loc) => named.push((
+                                name.clone(),
+                                interprete_instruction(value, loc.clone(), contexes),
+                            )),
+                            (x, loc) => args.push(interprete_instruction(x, loc.clone(), contexes)),
+                        }
+                    }
+                    ExprValue::Args(args, named)
+                }
+                RASTNode::MethodCall(name, body, methods) => {
+                    let (args, named) = interprete_args(body.clone(), contexes.clone());
+                    ExprValue::MethodCall(name.clone(), args, named, methods.clone())
                 }
                 RASTNode::Member(name) => ExprValue::Member(name.clone()),
                 x => ExprValue::Value(interprete_instruction(x, location.clone(), contexes)),
             }),
-            RExprTerm::Op(op) => match op {
-                Operator::Interpretation => {
-                    let right = stack.pop().unwrap();
-                    let left = stack.pop().unwrap();
-                    if let ExprValue::Value(VariableValue::Type(into)) = right {
-                        if let ExprValue::Value(VariableValue::Instance(of, values)) = left {
-                            let interpretation = of
-                                .borrow()
-                                .interpretations
-                                .iter()
-                                .find(|x| x.0.upgrade().map(|y| *y == *into).unwrap_or(false))
-                                .map(|x| x.clone());
+            RExprTerm::Op(op) => execute_op(op, &mut stack, location.clone(), contexes),
+        }
+        if signal::is_raised(contexes) {
+            return vec![ExprValue::Value(VariableValue::Nil)];
+        }
+    }
 
-                            if let Some(interpretation) = interpretation {
-                                stack.push(ExprValue::Value(
-                                    interpretation::interprete_interpretation(
-                                        VariableValue::Instance(of.clone(), values),
-                                        interpretation.clone(),
-                                    ),
-                                ));
-                            } else if of.borrow().can_turn_into(into.clone()) {
-                                stack.push(ExprValue::Value(interpretation::cast_value(
-                                    VariableValue::Instance(of.clone(), values),
-                                    into
-                                )));
-                            }
-                        } else {
-                            CompError::new(
-                                1,
-                                "Casting non-struct-instances to other objects is not yet supported!".to_string(),
-                                CompLocation::from(location)
-                            ).print_and_exit();
-                        }
-                    } else {
-                        CompError::new(
-                            204,
-                            "Trying to cast to a non-type".to_string(),
+    stack
+}
+
+/** Executes the operator `op` on the top of the ExprValue stack `stack`, pushing its result back onto it.
+    This is shared with the bytecode VM, whose value stack is made of `ExprValue`s as well.
+**/
+pub fn execute_op<'a>(
+    op: &Operator,
+    stack: &mut Vec<ExprValue<'a>>,
+    location: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) {
+    match op {
+        Operator::Interpretation => {
+            let right = stack.pop().unwrap();
+            let left = stack.pop().unwrap();
+            if let ExprValue::Value(VariableValue::Type(into)) = right {
+                if let ExprValue::Value(VariableValue::Instance(of, values) | VariableValue::Super(_, of, values)) = left {
+                    let interpretation = of.borrow().get_interpretation(&into);
+
+                    if let Some(interpretation) = interpretation {
+                        stack.push(ExprValue::Value(
+                            interpretation::interprete_interpretation(
+                                VariableValue::Instance(of.clone(), values),
+                                &interpretation,
+                                location.clone(),
+                                contexes,
+                            ),
+                        ));
+                    } else if of.borrow().is_descendant_of(&into) {
+                        // turning an instance into one of its ancestors keeps its struct, so that its overrides are still called
+                        stack.push(ExprValue::Value(VariableValue::Instance(of.clone(), values)));
+                    } else if of.borrow().can_turn_into(into.clone()) {
+                        stack.push(ExprValue::Value(interpretation::cast_value(
+                            VariableValue::Instance(of.clone(), values),
+                            into
+                        )));
+                    }
+                } else {
+                    CompError::new(
+                        1,
+                        "Casting non-struct-instances to other objects is not yet supported!".to_string(),
+                        CompLocation::from(location)
+                    ).print_and_exit();
+                }
+            } else {
+                CompError::new(
+                    204,
+                    "Trying to cast to a non-type".to_string(),
+                    CompLocation::from(location),
+                )
+                .print_and_exit();
+            }
+        }
+        Operator::MemberAccessor => {
+            let right = stack.pop().unwrap();
+            let left = stack.pop().unwrap();
+            let (left, right) = match (left, right) {
+                (ExprValue::Value(value), ExprValue::MethodCall(name, args, named, methods))
+                    if Primitive::of(&value).is_some() =>
+                {
+                    let res = call_primitive_method(value, &name, args, named, &methods, location, contexes);
+                    stack.push(ExprValue::Value(res));
+                    return;
+                }
+                operands => operands,
+            };
+            match left {
+                ExprValue::Value(VariableValue::Function(fun, closure)) => {
+                    let (args, named) = match right {
+                        ExprValue::Value(VariableValue::Tuple(vec)) => (vec, vec![]),
+                        ExprValue::Value(VariableValue::Nil) => (vec![], vec![]),
+                        ExprValue::Value(x) => (vec![x], vec![]),
+                        ExprValue::Args(args, named) => (args, named),
+                        _ => CompError::new(
+                            1,
+                            "Accessing a member of a function is not yet supported"
+                                .to_string(),
                             CompLocation::from(location),
                         )
-                        .print_and_exit();
-                    }
+                        .print_and_exit(),
+                    };
+                    stack.push(ExprValue::Value(fun.call_named(
+                        args,
+                        named,
+                        location.clone(),
+                        contexes,
+                        closure,
+                        None,
+                    )));
                 }
-                Operator::MemberAccessor => {
-                    let right = stack.pop().unwrap();
-                    let left = stack.pop().unwrap();
-                    match left {
-                        ExprValue::Value(VariableValue::Function(fun, closure)) => {
-                            let args = match right {
-                                ExprValue::Value(VariableValue::Tuple(vec)) => vec,
-                                ExprValue::Value(VariableValue::Nil) => vec![],
-                                ExprValue::Value(x) => vec![x],
-                                _ => CompError::new(
-                                    1,
-                                    "Accessing a member of a function is not yet supported"
-                                        .to_string(),
-                                    CompLocation::from(location),
-                                )
-                                .print_and_exit(),
-                            };
-                            stack.push(ExprValue::Value(fun.call(
+                ExprValue::Value(VariableValue::Type(t)) => match right {
+                    ExprValue::Member(name) => {
+                        stack.push(ExprValue::Value(instance::get_static(&t, &name, location.clone(), contexes)))
+                    }
+                    ExprValue::MethodCall(name, args, named, _) => {
+                        let method = t.borrow().get_method(name.clone());
+                        if let Some(fun) = method {
+                            // methods taking #self() are rejected by `RFunction::call_named`, once the overload is known
+                            stack.push(ExprValue::Value(fun.call_named(
                                 args,
+                                named,
                                 location.clone(),
                                 contexes,
-                                closure,
+                                vec![],
+                                Some(VariableValue::Type(t.clone())),
                             )));
-                        }
-                        ExprValue::Value(VariableValue::Type(t)) => match right {
-                            ExprValue::Member(_name) => CompError::new(
-                                1,
-                                "Accessing a member of a type is not yet supported".to_string(),
+                        } else {
+                            CompError::new(
+                                152,
+                                format!("Couldn't find method {} in object", name),
                                 CompLocation::from(location),
                             )
-                            .print_and_exit(),
-                            ExprValue::MethodCall(name, args) => {
-                                if let Some(fun) = t.borrow().get_method(name.clone()) {
-                                    stack.push(ExprValue::Value(fun.call_member(
-                                        match interprete(args, contexes.clone()) {
-                                            VariableValue::Tuple(list) => list,
-                                            x => vec![x],
-                                        },
-                                        location.clone(),
-                                        contexes,
-                                        vec![],
-                                        Some(VariableValue::Type(t.clone())),
-                                    )));
-                                } else {
-                                    CompError::new(
-                                        152,
-                                        format!("Couldn't find method {} in object", name),
-                                        CompLocation::from(location),
-                                    )
-                                    .print_and_exit()
-                                }
-                            }
-                            _ => CompError::new(
-                                1,
-                                format!("Complex accessors are not yet supported!"),
+                            .print_and_exit()
+                        }
+                    }
+                    _ => CompError::new(
+                        1,
+                        format!("Complex accessors are not yet supported!"),
+                        CompLocation::from(location),
+                    )
+                    .print_and_exit(),
+                },
+                ExprValue::Value(VariableValue::Instance(t, vars)) => match right {
+                    ExprValue::Member(name) => {
+                        stack.push(ExprValue::Value(instance::get_field(&t, &vars, &name, location.clone())))
+                    }
+                    ExprValue::MethodCall(name, args, named, _) => {
+                        let method = t.borrow().get_method(name.clone());
+                        if let Some(fun) = method {
+                            stack.push(ExprValue::Value(fun.call_named(
+                                args,
+                                named,
+                                location.clone(),
+                                contexes,
+                                vec![],
+                                Some(VariableValue::Instance(t.clone(), vars.clone())),
+                            )));
+                        } else {
+                            CompError::new(
+                                152,
+                                format!(
+                                    "Cannot find method {} in object of type {}.",
+                                    name,
+                                    t.borrow().name.clone()
+                                ),
                                 CompLocation::from(location),
                             )
-                            .print_and_exit(),
-                        },
-                        ExprValue::Value(VariableValue::Instance(t, vars)) => match right {
-                            ExprValue::Member(name) => {
-                                stack.push(ExprValue::Value(resolve_access(vars, name)))
-                            }
-                            ExprValue::MethodCall(name, args) => {
-                                if let Some(fun) = t.borrow().get_method(name.clone()) {
-                                    stack.push(ExprValue::Value(fun.call_member(
-                                        match interprete(args, contexes.clone()) {
-                                            VariableValue::Tuple(list) => list,
-                                            x => vec![x],
-                                        },
-                                        location.clone(),
-                                        contexes,
-                                        vec![],
-                                        Some(VariableValue::Instance(t.clone(), vars.clone())),
-                                    )));
-                                } else {
-                                    CompError::new(
-                                        152,
-                                        format!(
-                                            "Cannot find method {} in object of type {}.",
-                                            name,
-                                            t.borrow().name.clone()
-                                        ),
-                                        CompLocation::from(location),
-                                    )
-                                    .print_and_exit();
-                                }
-                            }
-                            _ => CompError::new(
-                                1,
-                                format!("Complex accessors are not yet supported!"),
+                            .print_and_exit();
+                        }
+                    }
+                    _ => CompError::new(
+                        1,
+                        format!("Complex accessors are not yet supported!"),
+                        CompLocation::from(location),
+                    )
+                    .print_and_exit(),
+                },
+                ExprValue::Value(VariableValue::Super(from, t, vars)) => match right {
+                    ExprValue::Member(name) => {
+                        stack.push(ExprValue::Value(instance::get_field(&t, &vars, &name, location.clone())))
+                    }
+                    ExprValue::MethodCall(name, args, named, _) => {
+                        // the method is the parent's, but it is still called on `self`
+                        let method = from.borrow().get_method(name.clone());
+                        if let Some(fun) = method {
+                            stack.push(ExprValue::Value(fun.call_named(
+                                args,
+                                named,
+                                location.clone(),
+                                contexes,
+                                vec![],
+                                Some(VariableValue::Instance(t.clone(), vars.clone())),
+                            )));
+                        } else {
+                            CompError::new(
+                                152,
+                                format!(
+                                    "Cannot find method {} in object of type {}.",
+                                    name,
+                                    from.borrow().name.clone()
+                                ),
                                 CompLocation::from(location),
                             )
-                            .print_and_exit(),
-                        },
-                        ExprValue::Value(VariableValue::Tuple(vec)) => match right {
-                            ExprValue::Value(VariableValue::Number(x)) => {
+                            .print_and_exit();
+                        }
+                    }
+                    _ => CompError::new(
+                        1,
+                        "Complex accessors are not yet supported!".to_string(),
+                        CompLocation::from(location),
+                    )
+                    .print_and_exit(),
+                },
+                ExprValue::Value(VariableValue::Tuple(vec)) => match right {
+                    ExprValue::Value(VariableValue::Number(x)) => {
+                        let index = x as usize;
+                        if index >= vec.len() {
+                            stack.push(ExprValue::Value(VariableValue::Nil));
+                        } else {
+                            stack.push(ExprValue::Value(vec[index].clone()));
+                        }
+                    }
+                    ExprValue::Value(VariableValue::Tuple(vec2)) => {
+                        if vec2.len() == 1 {
+                            if let VariableValue::Number(x) = vec2[0] {
                                 let index = x as usize;
                                 if index >= vec.len() {
                                     stack.push(ExprValue::Value(VariableValue::Nil));
                                 } else {
                                     stack.push(ExprValue::Value(vec[index].clone()));
                                 }
+                            } else {
+                                stack.push(ExprValue::Value(VariableValue::Nil));
                             }
-                            ExprValue::Value(VariableValue::Tuple(vec2)) => {
-                                if vec2.len() == 1 {
-                                    if let VariableValue::Number(x) = vec2[0] {
-                                        let index = x as usize;
-                                        if index >= vec.len() {
-                                            stack.push(ExprValue::Value(VariableValue::Nil));
-                                        } else {
-                                            stack.push(ExprValue::Value(vec[index].clone()));
-                                        }
+                        } else {
+                            stack.push(ExprValue::Value(VariableValue::Tuple(vec2.into_iter().map(|raw| {
+                                if let VariableValue::Number(x) = raw {
+                                    let index = x as usize;
+                                    if index >= vec.len() {
+                                        VariableValue::Nil
                                     } else {
-                                        stack.push(ExprValue::Value(VariableValue::Nil));
+                                        vec[index].clone()
                                     }
                                 } else {
-                                    stack.push(ExprValue::Value(VariableValue::Tuple(vec2.into_iter().map(|raw| {
-                                        if let VariableValue::Number(x) = raw {
-                                            let index = x as usize;
-                                            if index >= vec.len() {
-                                                VariableValue::Nil
-                                            } else {
-                                                vec[index].clone()
-                                            }
-                                        } else {
-                                            VariableValue::Nil
-                                        }
-                                    }).collect())));
+                                    VariableValue::Nil
                                 }
-                            }
-                            _ => CompError::new(
-                                205,
-                                String::from("Invalid tuple member accessor!"),
-                                CompLocation::from(location),
-                            )
-                            .print_and_exit(),
-                        },
-                        _ => CompError::new(
-                            1,
-                            format!("Accessing members of this data type is not yet supported!"),
-                            CompLocation::from(location),
-                        )
-                        .print_and_exit(),
-                    }
-                }
-                Operator::PartialApplication => {
-                    let right = stack.pop().unwrap();
-                    let left = stack.pop().unwrap();
-                    if let ExprValue::PartialTuple(vec) = right {
-                        if let ExprValue::Value(VariableValue::Function(f, closure)) = left {
-                            stack.push(ExprValue::Value(VariableValue::Function(
-                                Rc::new(PartialApplication {
-                                    parent: f,
-                                    args: vec,
-                                }),
-                                closure
-                            )));
+                            }).collect())));
                         }
-                    } else {
-                        panic!("Right-hand side of partial application resolved to non-PartialTuple");
                     }
+                    _ => CompError::new(
+                        205,
+                        String::from("Invalid tuple member accessor!"),
+                        CompLocation::from(location),
+                    )
+                    .print_and_exit(),
+                },
+                _ => CompError::new(
+                    1,
+                    format!("Accessing members of this data type is not yet supported!"),
+                    CompLocation::from(location),
+                )
+                .print_and_exit(),
+            }
+        }
+        Operator::PartialApplication => {
+            let right = stack.pop().unwrap();
+            let left = stack.pop().unwrap();
+            if let ExprValue::PartialTuple(vec) = right {
+                if let ExprValue::Value(VariableValue::Function(f, closure)) = left {
+                    stack.push(ExprValue::Value(VariableValue::Function(
+                        Rc::new(PartialApplication {
+                            parent: f,
+                            args: vec,
+                        }),
+                        closure
+                    )));
                 }
-                Operator::Not => {
-                    let res = execute_unary_op(stack.pop().unwrap(), &op, location.clone());
-                    stack.push(res);
-                }
-                _ => {
-                    let res = execute_bin_op(
-                        stack.pop().unwrap(),
-                        stack.pop().unwrap(),
-                        &op,
+            } else {
+                panic!("Right-hand side of partial application resolved to non-PartialTuple");
+            }
+        }
+        Operator::Pipe => {
+            let right = stack.pop().unwrap();
+            let left = stack.pop().unwrap();
+            match (left, right) {
+                (ExprValue::Value(value), ExprValue::Value(VariableValue::Function(f, closure))) => {
+                    stack.push(ExprValue::Value(f.call_named(
+                        vec![value],
+                        vec![],
                         location.clone(),
-                    );
-                    stack.push(res);
+                        contexes,
+                        closure,
+                        None,
+                    )));
+                }
+                (_, right) => err_not_function(op, right, location),
+            }
+        }
+        Operator::Compose => {
+            let right = stack.pop().unwrap();
+            let left = stack.pop().unwrap();
+            match (left, right) {
+                (
+                    ExprValue::Value(VariableValue::Function(f, f_closure)),
+                    ExprValue::Value(VariableValue::Function(g, g_closure)),
+                ) => {
+                    stack.push(ExprValue::Value(VariableValue::Function(
+                        composite_fn::CompositeFunction::new(f, f_closure, g, g_closure, *op),
+                        vec![],
+                    )));
+                }
+                (ExprValue::Value(VariableValue::Function(_, _)), right) => {
+                    err_not_function(op, right, location)
                 }
-            },
+                (left, _) => err_not_function(op, left, location),
+            }
+        }
+        Operator::Not => {
+            let res = execute_unary_op(stack.pop().unwrap(), &op, location.clone());
+            stack.push(res);
+        }
+        _ => {
+            let res = execute_bin_op(
+                stack.pop().unwrap(),
+                stack.pop().unwrap(),
+                &op,
+                location.clone(),
+            );
+            stack.push(res);
         }
     }
-
-    stack
 }
 
 /**
@@ -313,6 +427,46 @@ pub fn interprete_expression<'a>(
     }
 }
 
+fn call_primitive_method<'a>(
+    value: VariableValue<'a>,
+    name: &str,
+    args: Vec<VariableValue<'a>>,
+    named: Vec<(String, VariableValue<'a>)>,
+    methods: &PrimitiveMethodsRef<'a>,
+    location: Location<'a>,
+    contexes: &Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    //! Calls the method `name` on a value of a primitive type, ie. `"abc".'len()`, be it built-in or added by an extension
+    let primitive = Primitive::of(&value).unwrap();
+    let method = methods.borrow().lookup(primitive, name);
+    match method {
+        Some(fun) => fun.call_named(args, named, location, contexes, vec![], Some(value)),
+        None => CompError::new(
+            152,
+            format!("Cannot find method {} of type {}", name, primitive),
+            CompLocation::from(location),
+        )
+        .append(
+            format!("Methods may be added to {} with `extends {} {{...}}`", primitive, primitive),
+            CompLocation::None,
+        )
+        .print_and_exit(),
+    }
+}
+
+fn err_not_function<'a>(op: &Operator, value: ExprValue<'a>, location: Location<'a>) -> ! {
+    let value = match value {
+        ExprValue::Value(value) => value.to_string(),
+        _ => String::from("a member"),
+    };
+    CompError::new(
+        216,
+        format!("Expected a function as operand of {}, got {}", op, value),
+        CompLocation::from(location),
+    )
+    .print_and_exit()
+}
+
 // TODO: move these back to interprete_expr_int?
 
 /** Executes the binary operator `op` on `a` and `b` **/
@@ -343,14 +497,6 @@ pub fn execute_unary_op<'a>(
     }
 }
 
-/** Looks up `name` in `obj` as part of a member access expression **/
-fn resolve_access<'a>(obj: InstanceRef<'a>, name: String) -> VariableValue<'a> {
-    obj.borrow()
-        .get(&name)
-        .map(|x| x.clone())
-        .unwrap_or(VariableValue::Nil)
-}
-
 pub struct PartialApplication<'a> {
     pub parent: Rc<dyn Callable<'a> + 'a>,
     pub args: Vec<PartialValue<'a>>,
@@ -361,6 +507,19 @@ impl<'a> Callable<'a> for PartialApplication<'a> {
         format!("Partial<{}>", self.parent.get_name())
     }
 
+    fn get_args_n(&self) -> Option<usize> {
+        Some(
+            self.args
+                .iter()
+                .filter(|arg| matches!(arg, PartialValue::Void))
+                .count(),
+        )
+    }
+
+    fn get_location(&self) -> Option<Location<'a>> {
+        self.parent.get_location()
+    }
+
     fn call_member(
         &self,
         args: Vec<VariableValue<'a>>,
@@ -368,9 +527,21 @@ impl<'a> Callable<'a> for PartialApplication<'a> {
         contexes: &Vec<ContextRef<'a>>,
         closure: Vec<(String, VariableValue<'a>)>,
         parent: Option<VariableValue<'a>>,
+    ) -> VariableValue<'a> {
+        self.call_named(args, vec![], location, contexes, closure, parent)
+    }
+
+    fn call_named(
+        &self,
+        args: Vec<VariableValue<'a>>,
+        named: Vec<(String, VariableValue<'a>)>,
+        location: Location<'a>,
+        contexes: &Vec<ContextRef<'a>>,
+        closure: Vec<(String, VariableValue<'a>)>,
+        parent: Option<VariableValue<'a>>,
     ) -> VariableValue<'a> {
         let mut args_iter = args.into_iter();
-        let args = self.args.iter().map(|arg| match arg {
+        let mut args: Vec<VariableValue<'a>> = self.args.iter().map(|arg| match arg {
             PartialValue::VariableValue(x) => x.clone(),
             PartialValue::Void => args_iter.next().unwrap_or_else(|| {
                 CompError::new(
@@ -380,7 +551,9 @@ impl<'a> Callable<'a> for PartialApplication<'a> {
                 ).print_and_exit()
             })
         }).collect();
+        // the remaining arguments are appended, to fill in default-valued or #rest() arguments
+        args.extend(args_iter);
 
-        self.parent.call_member(args, location, contexes, closure, parent)
+        self.parent.call_named(args, named, location, contexes, closure, parent)
     }
 }
@@ -0,0 +1,180 @@
+//! Creation of struct instances and access to their fields and to the static members of structs, shared by the interpreter and the VM.
+
+use super::*;
+use crate::vm;
+use std::collections::HashMap;
+
+/** Creates an instance of `st`, whose fields hold their default value.
+    @param fields - Values overriding the defaults, as given by a struct literal (`Point { x: 1, y: 2 }`)
+    @param contexes - The contexes in which the default values are evaluated
+
+    The `let x: value` declarations of the struct's body are evaluated in order, each time an instance is created; fields declared without a value default to `Nil`.
+**/
+pub fn instantiate<'a>(
+    st: RStructRef<'a>,
+    fields: Vec<(String, VariableValue<'a>)>,
+    location: Location<'a>,
+    contexes: &[ContextRef<'a>],
+) -> VariableValue<'a> {
+    let mut vars = defaults(&st, contexes);
+    for (name, value) in fields {
+        if !st.borrow().has_field(&name) {
+            unknown_field(&st, &name, location.clone());
+        }
+        vars.insert(name, value);
+    }
+
+    VariableValue::Instance(st, Rc::new(RefCell::new(vars)))
+}
+
+fn defaults<'a>(st: &RStructRef<'a>, contexes: &[ContextRef<'a>]) -> HashMap<String, VariableValue<'a>> {
+    //! Evaluates the `let` declarations of the struct's body into a new map of fields, after those of its ancestors
+    let parent = st.borrow().parent.clone();
+    let mut vars = match parent {
+        Some(parent) => defaults(&parent, contexes),
+        None => HashMap::new(),
+    };
+    let body = st.borrow().context.clone();
+    if let Some(body) = body {
+        let ctx = Rc::new(RefCell::new(Context::from(body.clone())));
+        let mut contexes = contexes.to_vec();
+        contexes.push(ctx.clone());
+        if vm::is_active() {
+            vm::evaluate_defaults(st, contexes);
+        } else {
+            for (instruction, location) in &body.borrow().instructions {
+                if let RASTNode::VariableDef(var, _) = instruction {
+                    if !var.ast_ref.borrow().is_static {
+                        interprete_instruction(instruction, location.clone(), &contexes);
+                    }
+                }
+            }
+        }
+        for (variable, value) in body.borrow().variables.iter().zip(ctx.borrow().variables.iter()) {
+            if !variable.borrow().is_static {
+                vars.insert(variable.borrow().name.clone(), value.clone());
+            }
+        }
+    }
+    vars
+}
+
+/** Returns the static members declared in the body of `st`, initialising them on first access.
+    Static members are declared with `static name: value` and are read and set through the type (`Math.half`); they are stored once for the whole program, and are not fields of the instances of `st`.
+    They are initialised in order, so that each of them may read the previous ones.
+**/
+fn statics<'a>(st: &RStructRef<'a>, contexes: &[ContextRef<'a>]) -> InstanceRef<'a> {
+    if let Some(statics) = st.borrow().statics.clone() {
+        return statics;
+    }
+    let statics = Rc::new(RefCell::new(HashMap::new()));
+    st.borrow_mut().statics = Some(statics.clone());
+    let body = st.borrow().context.clone();
+    if let Some(body) = body {
+        let ctx = Rc::new(RefCell::new(Context::from(body.clone())));
+        let mut contexes = contexes.to_vec();
+        contexes.push(ctx.clone());
+        for (instruction, location) in &body.borrow().instructions {
+            if let RASTNode::VariableDef(var, _) = instruction {
+                if var.ast_ref.borrow().is_static {
+                    if vm::is_active() {
+                        vm::evaluate_instruction(&body, instruction, location.clone(), contexes.clone());
+                    } else {
+                        interprete_instruction(instruction, location.clone(), &contexes);
+                    }
+                    let value = ctx.borrow().variables[var.slot].clone();
+                    statics.borrow_mut().insert(var.name.clone(), value);
+                }
+            }
+        }
+    }
+    statics
+}
+
+fn static_owner<'a>(st: &RStructRef<'a>, name: &str, location: Location<'a>) -> RStructRef<'a> {
+    //! Returns `st` or its ancestor whose body declares the static member `name`, erroring out if there is none
+    let mut current = Some(st.clone());
+    while let Some(owner) = current {
+        if owner.borrow().has_static(name) {
+            return owner;
+        }
+        current = owner.borrow().parent.clone();
+    }
+    let mut error = CompError::new(
+        209,
+        format!("Unknown static member {} in struct {}", name, st.borrow().name),
+        CompLocation::from(location),
+    );
+    if st.borrow().has_field(name) {
+        error = error.append(
+            format!("{} is a field, so it must be read on an instance; use `static` to declare it on the type instead", name),
+            CompLocation::None,
+        );
+    }
+    error.print_and_exit()
+}
+
+/** Reads the static member `name` of `st`, erroring out if neither `st` nor its ancestors declare it **/
+pub fn get_static<'a>(
+    st: &RStructRef<'a>,
+    name: &str,
+    location: Location<'a>,
+    contexes: &[ContextRef<'a>],
+) -> VariableValue<'a> {
+    let owner = static_owner(st, name, location);
+    let statics = statics(&owner, contexes);
+    let value = statics.borrow().get(name).cloned().unwrap_or(VariableValue::Nil);
+    value
+}
+
+/** Sets the static member `name` of `st` to `value`, returning its previous value; errors out if neither `st` nor its ancestors declare it **/
+pub fn set_static<'a>(
+    st: &RStructRef<'a>,
+    name: &str,
+    value: VariableValue<'a>,
+    location: Location<'a>,
+    contexes: &[ContextRef<'a>],
+) -> VariableValue<'a> {
+    let owner = static_owner(st, name, location);
+    let statics = statics(&owner, contexes);
+    let old = statics.borrow_mut().insert(name.to_string(), value);
+    old.unwrap_or(VariableValue::Nil)
+}
+
+/** Reads the field `name` of an instance of `st`, erroring out if `st` does not declare it **/
+pub fn get_field<'a>(
+    st: &RStructRef<'a>,
+    vars: &InstanceRef<'a>,
+    name: &str,
+    location: Location<'a>,
+) -> VariableValue<'a> {
+    if !st.borrow().has_field(name) {
+        unknown_field(st, name, location);
+    }
+    vars.borrow().get(name).cloned().unwrap_or(VariableValue::Nil)
+}
+
+/** Sets the field `name` of an instance of `st` to `value`, returning its previous value; errors out if `st` does not declare it **/
+pub fn set_field<'a>(
+    st: &RStructRef<'a>,
+    vars: &InstanceRef<'a>,
+    name: &str,
+    value: VariableValue<'a>,
+    location: Location<'a>,
+) -> VariableValue<'a> {
+    if !st.borrow().has_field(name) {
+        unknown_field(st, name, location);
+    }
+    vars.borrow_mut()
+        .insert(name.to_string(), value)
+        .unwrap_or(VariableValue::Nil)
+}
+
+fn unknown_field<'a>(st: &RStructRef<'a>, name: &str, location: Location<'a>) -> ! {
+    CompError::new(
+        209,
+        format!("Unknown field {} in struct {}", name, st.borrow().name),
+        CompLocation::from(location),
+    )
+    .print_and_exit()
+}
@@ -1,30 +1,34 @@
 use super::*;
+use crate::vm;
 use std::cell::RefCell;
-use std::collections::HashMap;
 
 /** Casts or interpretes `value` into an instance of `into`.
     @param value - The value to cast
-    @param into - The type to turn `value` into
-    @param body - The (constructed and resolved) interpretation's body
+    @param interpretation - The (constructed and resolved) interpretation of `value`'s struct into the target type
+    @param contexes - The contexes in which the default values of the new instance's fields are evaluated
 **/
 pub fn interprete_interpretation<'a>(
     value: VariableValue<'a>,
-    (into, body): (RStructWeak<'a>, RASTRef<'a>),
+    interpretation: &RInterpretation<'a>,
+    location: Location<'a>,
+    contexes: &[ContextRef<'a>],
 ) -> VariableValue<'a> {
+    let body = &interpretation.body;
     let mut init_ctx = Context::from(body.clone());
-    init_ctx.variables.insert(String::from("from"), value);
-    let res = VariableValue::Instance(
-        into.upgrade().unwrap(),
-        Rc::new(RefCell::new(HashMap::new())),
-    );
-    init_ctx.variables.insert(String::from("to"), res.clone());
+    // `from` and `to` are declared in that order by `RStruct::add_interpretation`
+    init_ctx.set(0, value);
+    let res = instance::instantiate(interpretation.into.upgrade().unwrap(), vec![], location, contexes);
+    init_ctx.set(1, res.clone());
 
-    match body.borrow().instructions.last() {
-        Some((RASTNode::Block(body), _)) => {
-            interprete(body.clone(), vec![Rc::new(RefCell::new(init_ctx))])
-        }
-        _ => panic!("Expected interpretation body to end with a block"),
-    };
+    let init = vec![Rc::new(RefCell::new(init_ctx))];
+    if vm::is_active() {
+        vm::interprete_interpretation(interpretation, init);
+    } else {
+        match body.borrow().instructions.last() {
+            Some((RASTNode::Block(body), _)) => interprete(body.clone(), init),
+            _ => panic!("Expected interpretation body to end with a block"),
+        };
+    }
 
     res
 }
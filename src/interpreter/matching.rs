@@ -0,0 +1,73 @@
+//! Runs `#match` constructs, for both the interpreter and the VM.
+
+use super::*;
+use crate::vm;
+
+/** Evaluates the body of the first arm of `arms` whose pattern matches `value` and whose guard (if any) holds, within `contexes`.
+    Errors out if no arm matches.
+**/
+pub fn match_value<'a>(
+    value: VariableValue<'a>,
+    arms: &[RMatchArm<'a>],
+    location: Location<'a>,
+    contexes: &[ContextRef<'a>],
+) -> VariableValue<'a> {
+    for arm in arms {
+        let scope = Rc::new(RefCell::new(Context::from(arm.scope.clone())));
+        if !bind(&arm.pattern, &value, &mut scope.borrow_mut()) {
+            continue;
+        }
+        let mut contexes = contexes.to_vec();
+        contexes.push(scope);
+        if let Some(guard) = &arm.guard {
+            let holds = if vm::is_active() {
+                vm::match_guard(arm, contexes.clone())
+            } else {
+                interprete(guard.clone(), contexes.clone())
+            };
+            let holds = is_truthy(&holds);
+            if signal::is_raised(&contexes) {
+                return VariableValue::Nil;
+            }
+            if !holds {
+                continue;
+            }
+        }
+        return if vm::is_active() {
+            vm::match_body(arm, contexes)
+        } else {
+            interprete(arm.body.clone(), contexes)
+        };
+    }
+
+    CompError::new(
+        212,
+        format!("Unmatched value in #match: {}", value),
+        CompLocation::from(location),
+    )
+    .print_and_exit()
+}
+
+fn bind<'a>(pattern: &RMatchPattern<'a>, value: &VariableValue<'a>, scope: &mut Context<'a>) -> bool {
+    //! Returns wether or not `value` matches `pattern`, binding the names of the pattern in `scope` as it goes
+    match (pattern, value) {
+        (RMatchPattern::Wildcard, _) => true,
+        (RMatchPattern::Literal(literal), value) => literal == value,
+        (RMatchPattern::Bind(slot), value) => {
+            scope.set(*slot, value.clone());
+            true
+        }
+        (RMatchPattern::Tuple(patterns), VariableValue::Tuple(values)) => {
+            patterns.len() == values.len()
+                && patterns.iter().zip(values).all(|(pattern, value)| bind(pattern, value, scope))
+        }
+        (RMatchPattern::Struct(st, fields), VariableValue::Instance(t, vars) | VariableValue::Super(_, t, vars)) => {
+            t.borrow().conforms_to(st)
+                && fields.iter().all(|(name, pattern)| {
+                    let value = vars.borrow().get(name).cloned().unwrap_or(VariableValue::Nil);
+                    bind(pattern, &value, scope)
+                })
+        }
+        _ => false,
+    }
+}
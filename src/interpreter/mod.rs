@@ -2,28 +2,43 @@ pub use super::ast::resolve::*;
 use std::cell::RefCell;
 use std::ops::Deref;
 
+pub mod assign;
 pub mod callable;
 pub mod context;
 pub mod expr;
+pub mod instance;
 pub mod interpretation;
+pub mod matching;
+pub mod pretty;
+pub mod range;
+pub mod signal;
 pub mod value;
 pub mod composite_fn;
 
 pub use callable::*;
 pub use context::*;
 pub use expr::*;
+pub use pretty::*;
+pub use range::Range;
+pub use signal::Signal;
 pub use value::*;
 
 pub fn interprete<'a>(ast: RASTRef<'a>, contexes: Vec<ContextRef<'a>>) -> VariableValue<'a> {
-    //! Interpretes an `RAST` block
+    /*! Interpretes an `RAST` block
+    Stops as soon as a control flow signal is raised; it is then taken back by whoever it targets, or by this function if it runs the whole program.
+    */
+    let is_program = contexes.is_empty();
     let mut contexes = contexes.clone();
     contexes.push(Rc::new(RefCell::new(Context::from(ast.clone()))));
     let mut last_value: VariableValue = VariableValue::Nil;
 
-    if let ASTKind::Tuple | ASTKind::ArgTuple = ast.borrow().kind {
+    let value = if let ASTKind::Tuple | ASTKind::ArgTuple = ast.borrow().kind {
         let mut res: Vec<VariableValue<'a>> = Vec::new();
         for instruction in &ast.borrow().instructions {
             last_value = interprete_instruction(&instruction.0, instruction.1.clone(), &contexes);
+            if signal::is_raised(&contexes) {
+                break;
+            }
             contexes.last().unwrap().borrow_mut().last_value = last_value.clone();
             // println!("=> {:?}", last_value);
             res.push(last_value);
@@ -33,14 +48,101 @@ pub fn interprete<'a>(ast: RASTRef<'a>, contexes: Vec<ContextRef<'a>>) -> Variab
     } else {
         for instruction in &ast.borrow().instructions {
             last_value = interprete_instruction(&instruction.0, instruction.1.clone(), &contexes);
+            if signal::is_raised(&contexes) {
+                break;
+            }
             contexes.last().unwrap().borrow_mut().last_value = last_value.clone();
             // println!("-> {:?}", last_value);
         }
 
         last_value
+    };
+
+    if is_program {
+        signal::finish(value, &contexes)
+    } else {
+        value
+    }
+}
+
+pub fn interprete_tail<'a>(
+    ast: RASTRef<'a>,
+    contexes: Vec<ContextRef<'a>>,
+    caller: &Vec<ContextRef<'a>>,
+) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+    /*! Interpretes the body of a function, like `interprete` does.
+    The call made by its last instruction is returned as a `TailCall` if it can be made from `caller` instead, so that the function's frame gets reused.
+    */
+    let mut contexes = contexes;
+    contexes.push(Rc::new(RefCell::new(Context::from(ast.clone()))));
+    let ast = ast.borrow();
+
+    match ast.instructions.split_last() {
+        Some(((last, location), instructions)) => {
+            for instruction in instructions {
+                let last_value =
+                    interprete_instruction(&instruction.0, instruction.1.clone(), &contexes);
+                if signal::is_raised(&contexes) {
+                    return Ok(VariableValue::Nil);
+                }
+                contexes.last().unwrap().borrow_mut().last_value = last_value;
+            }
+
+            if let RASTNode::PatternCall(pat, args) = last {
+                let (args, named) = interprete_args(args.clone(), contexes.clone());
+                if signal::is_raised(&contexes) {
+                    Ok(VariableValue::Nil)
+                } else if pat.is_tail_callable(caller) {
+                    Err(Box::new(TailCall {
+                        function: pat.clone(),
+                        args,
+                        named,
+                        closure: vec![],
+                        location: location.clone(),
+                    }))
+                } else {
+                    pat.call_tail(args, named, location.clone(), &contexes, vec![], caller)
+                }
+            } else {
+                Ok(interprete_instruction(last, location.clone(), &contexes))
+            }
+        }
+        None => Ok(VariableValue::Nil),
     }
 }
 
+pub fn interprete_args<'a>(
+    ast: RASTRef<'a>,
+    contexes: Vec<ContextRef<'a>>,
+) -> (Vec<VariableValue<'a>>, Vec<(String, VariableValue<'a>)>) {
+    //! Interpretes the argument tuple of a call, returning its positional and its named arguments
+    let mut contexes = contexes.clone();
+    contexes.push(Rc::new(RefCell::new(Context::from(ast.clone()))));
+    let mut args: Vec<VariableValue<'a>> = Vec::new();
+    let mut named: Vec<(String, VariableValue<'a>)> = Vec::new();
+
+    for instruction in &ast.borrow().instructions {
+        let last_value = match &instruction.0 {
+            RASTNode::NamedArg(name, value) => {
+                let value = interprete_instruction(value, instruction.1.clone(), &contexes);
+                named.push((name.clone(), value.clone()));
+                value
+            }
+            x => {
+                let value = interprete_instruction(x, instruction.1.clone(), &contexes);
+                args.push(value.clone());
+                value
+            }
+        };
+        if signal::is_raised(&contexes) {
+            break;
+        }
+        contexes.last().unwrap().borrow_mut().last_value = last_value;
+    }
+
+    (args, named)
+}
+
 pub fn interprete_instruction<'a, 'b>(
     instruction: &'b RASTNode<'a>,
     location: Location<'a>,
@@ -50,16 +152,34 @@ pub fn interprete_instruction<'a, 'b>(
     match &instruction {
         RASTNode::Number(x) => VariableValue::Number(*x),
         RASTNode::String(x) => VariableValue::String(x.clone()),
+        RASTNode::Interpolation(parts) => {
+            let mut res = String::new();
+            for (part, loc) in parts {
+                let value = interprete_instruction(part, loc.clone(), contexes);
+                if signal::is_raised(contexes) {
+                    return VariableValue::Nil;
+                }
+                res.push_str(&value.to_string());
+            }
+            VariableValue::String(res)
+        }
         RASTNode::Boolean(x) => VariableValue::Boolean(*x),
-        RASTNode::Tuple(instructions, _is_partial) => VariableValue::Tuple(
-            instructions
-                .iter()
-                .map(|ins| interprete_instruction(&ins.0, ins.1.clone(), contexes))
-                .collect(),
-        ),
+        RASTNode::Tuple(instructions, _is_partial) => {
+            let mut values = Vec::with_capacity(instructions.len());
+            for ins in instructions {
+                values.push(interprete_instruction(&ins.0, ins.1.clone(), contexes));
+                if signal::is_raised(contexes) {
+                    break;
+                }
+            }
+            VariableValue::Tuple(values)
+        }
         RASTNode::VariableDef(var, value) => {
             let res = with_variable(var, contexes, |var| var.clone(), location.clone());
             let value = interprete_instruction(value.deref(), location.clone(), contexes);
+            if signal::is_raised(contexes) {
+                return VariableValue::Nil;
+            }
             with_variable(
                 var,
                 contexes,
@@ -72,19 +192,21 @@ pub fn interprete_instruction<'a, 'b>(
             res
         }
         RASTNode::PatternCall(pat, args) => {
-            let args = interprete(args.clone(), contexes.clone());
-            pat.call(
-                match args {
-                    VariableValue::Tuple(list) => list,
-                    x => vec![x],
-                },
-                location.clone(),
-                contexes,
-                vec![],
-            )
+            let (args, named) = interprete_args(args.clone(), contexes.clone());
+            if signal::is_raised(contexes) {
+                return VariableValue::Nil;
+            }
+            pat.call_named(args, named, location.clone(), contexes, vec![], None)
         }
         RASTNode::Expression(expr) => interprete_expression(expr, location, contexes),
         RASTNode::Block(ast) => interprete(ast.clone(), contexes.clone()),
+        RASTNode::Match(value, arms) => {
+            let value = interprete(value.clone(), contexes.clone());
+            if signal::is_raised(contexes) {
+                return VariableValue::Nil;
+            }
+            matching::match_value(value, arms, location, contexes)
+        }
         RASTNode::Variable(var) => with_variable(var, contexes, |var| var.clone(), location),
         RASTNode::Nil => VariableValue::Nil,
         RASTNode::VoidSymbol => VariableValue::Nil,
@@ -98,27 +220,63 @@ pub fn interprete_instruction<'a, 'b>(
                 .collect(),
         ),
         RASTNode::TypeName(x) => VariableValue::Type(x.clone()),
+        RASTNode::StructLiteral(st, fields) => {
+            let fields = fields
+                .iter()
+                .map(|(name, value, loc)| {
+                    (name.clone(), interprete_instruction(value, loc.clone(), contexes))
+                })
+                .collect();
+            if signal::is_raised(contexes) {
+                return VariableValue::Nil;
+            }
+            instance::instantiate(st.clone(), fields, location, contexes)
+        }
         RASTNode::ComplexDef(expr, member, value) => {
-            if let DefineMember::Member(name) = member {
-                if let VariableValue::Instance(_t, vars) =
-                    interprete_expression(expr, location.clone(), contexes)
-                {
-                    // TODO: check that `name` is part of _t
-                    let res = vars
-                        .borrow()
-                        .get(name)
-                        .map(|x| x.clone())
-                        .unwrap_or(VariableValue::Nil);
-                    vars.borrow_mut().insert(
-                        name.clone(),
-                        interprete_instruction(value, location, contexes),
-                    );
-                    res
-                } else {
-                    panic!("Trying to set value on non-object");
+            if let RDefineMember::Member(name) = member {
+                // static members are set through the type
+                let object = interprete_expression(expr, location.clone(), contexes);
+                let res = match &object {
+                    VariableValue::Instance(t, vars) | VariableValue::Super(_, t, vars) => {
+                        instance::get_field(t, vars, name, location.clone())
+                    }
+                    VariableValue::Type(t) => instance::get_static(t, name, location.clone(), contexes),
+                    _ => panic!("Trying to set value on non-object"),
+                };
+                let value = interprete_instruction(value, location.clone(), contexes);
+                if signal::is_raised(contexes) {
+                    return VariableValue::Nil;
                 }
+                match object {
+                    VariableValue::Type(t) => instance::set_static(&t, name, value, location, contexes),
+                    VariableValue::Instance(t, vars) | VariableValue::Super(_, t, vars) => {
+                        instance::set_field(&t, &vars, name, value, location)
+                    }
+                    _ => unreachable!(),
+                };
+                res
             } else {
-                unimplemented!("DefineMember::*");
+                let value = interprete_instruction(value, location.clone(), contexes);
+                if signal::is_raised(contexes) {
+                    return VariableValue::Nil;
+                }
+                assign::define(expr, member, value, location, contexes)
+            }
+        }
+        RASTNode::CompoundDef(target, op, value) => {
+            if let RDefineTarget::Member(expr, name) = target {
+                let object = interprete_expression(expr, location.clone(), contexes);
+                let value = interprete_instruction(value, location.clone(), contexes);
+                if signal::is_raised(contexes) {
+                    return VariableValue::Nil;
+                }
+                assign::compound_member(object, name, *op, value, location, contexes)
+            } else {
+                let value = interprete_instruction(value, location.clone(), contexes);
+                if signal::is_raised(contexes) {
+                    return VariableValue::Nil;
+                }
+                assign::compound(target, *op, value, location, contexes)
             }
         }
         _ => VariableValue::Nil,
@@ -144,7 +302,7 @@ where
             return ctx
                 .borrow_mut()
                 .variables
-                .get_mut(&variable.name)
+                .get_mut(variable.slot)
                 .map(|mut x| func(&mut x))
                 .unwrap_or_else(|| {
                     CompError::new(
@@ -0,0 +1,131 @@
+use super::*;
+use std::fmt;
+
+const INDENT: &str = "  ";
+
+/** Debug-style representation of a `VariableValue`, used by `#debug`.
+    Unlike `Display`, it prints out the fields of instances, spreads nested tuples over several indented lines and shows the arity and declaring location of functions.
+
+    Instances which are already being printed higher up are replaced with `<cycle>`, as instances may reference each other.
+**/
+pub struct PrettyValue<'a, 'b>(pub &'b VariableValue<'a>);
+
+impl<'a> VariableValue<'a> {
+    pub fn pretty<'b>(&'b self) -> PrettyValue<'a, 'b> {
+        //! Returns a wrapper whose `Display` implementation pretty-prints this value
+        PrettyValue(self)
+    }
+}
+
+impl<'a, 'b> fmt::Display for PrettyValue<'a, 'b> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pretty(f, self.0, 0, &mut Vec::new())
+    }
+}
+
+/** Writes `value` at the indentation level `indent`.
+    @param visiting - The instances currently being printed, used to detect cyclic references
+**/
+fn write_pretty<'a>(
+    f: &mut fmt::Formatter<'_>,
+    value: &VariableValue<'a>,
+    indent: usize,
+    visiting: &mut Vec<*const ()>,
+) -> fmt::Result {
+    match value {
+        VariableValue::String(x) => write!(f, "{:?}", x),
+        VariableValue::Tuple(vec) => {
+            if vec.iter().any(is_nested) {
+                writeln!(f, "(")?;
+                for (index, element) in vec.iter().enumerate() {
+                    write!(f, "{}", INDENT.repeat(indent + 1))?;
+                    write_pretty(f, element, indent + 1, visiting)?;
+                    writeln!(f, "{}", if index + 1 < vec.len() { "," } else { "" })?;
+                }
+                write!(f, "{})", INDENT.repeat(indent))
+            } else {
+                write!(f, "(")?;
+                for (index, element) in vec.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write_pretty(f, element, indent, visiting)?;
+                }
+                write!(f, ")")
+            }
+        }
+        VariableValue::Instance(t, vars) | VariableValue::Super(_, t, vars) => {
+            let name = t.borrow().name.clone();
+            let ptr = Rc::as_ptr(vars) as *const ();
+            if visiting.contains(&ptr) {
+                return write!(f, "{} {{ <cycle> }}", name);
+            }
+
+            let fields = instance_fields(t, vars);
+            if fields.is_empty() {
+                return write!(f, "{} {{}}", name);
+            }
+
+            visiting.push(ptr);
+            writeln!(f, "{} {{", name)?;
+            for (index, (field, value)) in fields.iter().enumerate() {
+                write!(f, "{}{}: ", INDENT.repeat(indent + 1), field)?;
+                write_pretty(f, value, indent + 1, visiting)?;
+                writeln!(f, "{}", if index + 1 < fields.len() { "," } else { "" })?;
+            }
+            visiting.pop();
+            write!(f, "{}}}", INDENT.repeat(indent))
+        }
+        VariableValue::Function(fun, _) => {
+            write!(f, "[function {}", fun.get_name())?;
+            if let Some(n) = fun.get_args_n() {
+                write!(f, ", {} argument{}", n, if n == 1 { "" } else { "s" })?;
+            }
+            if let Some(loc) = fun.get_location() {
+                write!(f, ", declared at {}:{}:{}", loc.path, loc.line, loc.ch)?;
+            }
+            write!(f, "]")
+        }
+        x => write!(f, "{}", x),
+    }
+}
+
+/// Returns wether or not `value` should be spread over several lines when inside of a tuple
+fn is_nested(value: &VariableValue<'_>) -> bool {
+    match value {
+        VariableValue::Tuple(vec) => !vec.is_empty(),
+        VariableValue::Instance(_, _) | VariableValue::Super(_, _, _) => true,
+        _ => false,
+    }
+}
+
+/** Returns the fields of an instance: the ones declared in its struct come first, in order of declaration, followed by the remaining ones in alphabetical order.
+    The fields are cloned, as to release the borrow on `vars` before recursing into them.
+**/
+fn instance_fields<'a>(
+    t: &RStructRef<'a>,
+    vars: &InstanceRef<'a>,
+) -> Vec<(String, VariableValue<'a>)> {
+    let vars = vars.borrow();
+    let mut declared: Vec<String> = Vec::new();
+    if let Some(ctx) = &t.borrow().context {
+        for var in &ctx.borrow().variables {
+            declared.push(var.borrow().name.clone());
+        }
+    }
+
+    let mut res: Vec<(String, VariableValue<'a>)> = declared
+        .iter()
+        .filter_map(|name| vars.get(name).map(|value| (name.clone(), value.clone())))
+        .collect();
+
+    let mut rest: Vec<(String, VariableValue<'a>)> = vars
+        .iter()
+        .filter(|(name, _)| !declared.contains(name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    rest.sort_by(|a, b| a.0.cmp(&b.0));
+
+    res.append(&mut rest);
+    res
+}
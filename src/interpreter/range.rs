@@ -0,0 +1,80 @@
+//! Numeric ranges, built by `#range()` and iterated by `#for()`
+
+use super::*;
+use std::fmt;
+
+/// Tolerance on the number of steps of a range, so that fractional steps reach their bound despite rounding errors
+const EPSILON: f64 = 1e-9;
+
+/// The numbers from `from` to `to` (included, if it is reached), separated by `step`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Range {
+    pub from: f64,
+    pub to: f64,
+    pub step: f64,
+}
+
+impl Range {
+    pub fn new(from: f64, to: f64, step: Option<f64>, location: &Location) -> Range {
+        /*! Creates a range, whose step defaults to 1 or -1 depending on its direction, as with `#range(from, to)`; `#for(from, to, fn)` always counts up instead.
+        Errors out if the step is zero or if a bound isn't finite. A step going away from `to` yields an empty range.
+        */
+        let step = step.unwrap_or(if to < from { -1.0 } else { 1.0 });
+        if step == 0.0 || !step.is_finite() || !from.is_finite() || !to.is_finite() {
+            CompError::new(
+                215,
+                format!("Invalid range from {} to {} by {}", from, to, step),
+                CompLocation::from(location),
+            )
+            .append(
+                String::from("The bounds must be finite numbers and the step must be non-zero"),
+                CompLocation::None,
+            )
+            .print_and_exit();
+        }
+        Range { from, to, step }
+    }
+
+    pub fn len(&self) -> usize {
+        let steps = (self.to - self.from) / self.step;
+        if steps < -EPSILON {
+            0
+        } else {
+            (steps + EPSILON).floor() as usize + 1
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, x: f64) -> bool {
+        //! Returns wether or not `x` is one of the numbers of the range
+        let steps = (x - self.from) / self.step;
+        let index = steps.round();
+        (steps - index).abs() < EPSILON && index >= 0.0 && (index as usize) < self.len()
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = f64> {
+        //! Iterates over the numbers of the range; each one is computed from `from` as to not accumulate rounding errors
+        (0..self.len()).map(move |index| self.from + (index as f64) * self.step)
+    }
+
+    fn default_step(&self) -> f64 {
+        if self.to < self.from {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.from, self.to)?;
+        if self.step != self.default_step() {
+            write!(f, " by {}", self.step)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Control flow signals, raised by `#return()`, `#break()` and `#continue()`, for both the interpreter and the VM.
+//! A raised signal is kept apart from values, in the root context of the context stack: instructions stop being run until the function or the loop which it targets takes it back.
+
+use super::*;
+
+#[derive(Debug, Clone)]
+pub enum Signal<'a> {
+    /// Exits the innermost named function (see `Callable::catches_return`) with this value
+    Return(VariableValue<'a>),
+    /// Exits the innermost loop with this value
+    Break(VariableValue<'a>, Location<'a>),
+    /// Skips to the next iteration of the innermost loop
+    Continue(Location<'a>),
+}
+
+pub fn raise<'a>(signal: Signal<'a>, contexes: &[ContextRef<'a>]) {
+    if let Some(root) = contexes.first() {
+        root.borrow_mut().signal = Some(signal);
+    }
+}
+
+pub fn is_raised(contexes: &[ContextRef<'_>]) -> bool {
+    contexes
+        .first()
+        .is_some_and(|root| root.borrow().signal.is_some())
+}
+
+pub fn take_return<'a>(contexes: &[ContextRef<'a>]) -> Option<VariableValue<'a>> {
+    //! Takes the raised signal back if it is a `Return`, returning its value
+    let root = contexes.first()?;
+    let mut root = root.borrow_mut();
+    match root.signal.take() {
+        Some(Signal::Return(value)) => Some(value),
+        signal => {
+            root.signal = signal;
+            None
+        }
+    }
+}
+
+pub fn take_loop<'a>(contexes: &[ContextRef<'a>]) -> Option<Signal<'a>> {
+    //! Takes the raised signal back if it is a `Break` or a `Continue`
+    let root = contexes.first()?;
+    let mut root = root.borrow_mut();
+    match root.signal.take() {
+        Some(Signal::Return(value)) => {
+            root.signal = Some(Signal::Return(value));
+            None
+        }
+        signal => signal,
+    }
+}
+
+pub fn finish<'a>(value: VariableValue<'a>, contexes: &[ContextRef<'a>]) -> VariableValue<'a> {
+    /*! Takes back the signal left raised at the end of a program: `#return()` ends the program with its value.
+    Errors out if it is a `Break` or a `Continue`, which were raised outside of any loop.
+    */
+    let signal = contexes.first().and_then(|root| root.borrow_mut().signal.take());
+    match signal {
+        None => value,
+        Some(Signal::Return(value)) => value,
+        Some(Signal::Break(_, location)) => outside_loop("#break", location),
+        Some(Signal::Continue(location)) => outside_loop("#continue", location),
+    }
+}
+
+fn outside_loop(name: &str, location: Location<'_>) -> ! {
+    CompError::new(
+        214,
+        format!("Cannot {}() outside of a loop", name),
+        CompLocation::from(location),
+    )
+    .append(
+        String::from("Loops are #for, #loop and #while"),
+        CompLocation::None,
+    )
+    .print_and_exit()
+}
@@ -12,8 +12,10 @@ pub enum VariableValue<'a> {
     Number(f64),
     Boolean(bool),
     Instance(RStructRef<'a>, InstanceRef<'a>), // TODO
+    Super(RStructRef<'a>, RStructRef<'a>, InstanceRef<'a>), // struct at which method lookup starts, struct of `self`, fields of `self`
     Type(RStructRef<'a>),
     Tuple(Vec<VariableValue<'a>>),
+    Range(Range),
     Function(
         Rc<(dyn Callable<'a> + 'a)>,
         Vec<(String, VariableValue<'a>)>,
@@ -22,13 +24,36 @@ pub enum VariableValue<'a> {
     Bail,
 }
 
+impl<'a> VariableValue<'a> {
+    pub fn into_instance(self) -> VariableValue<'a> {
+        //! Turns `super` into the instance it stands for, as it is passed on: only `super.'method()` looks methods up from the parent
+        match self {
+            VariableValue::Super(_, t, vars) => VariableValue::Instance(t, vars),
+            x => x,
+        }
+    }
+}
+
 impl<'a> fmt::Display for VariableValue<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             VariableValue::String(x) => write!(f, "{}", x),
             VariableValue::Number(x) => write!(f, "{}", x),
             VariableValue::Boolean(x) => write!(f, "{}", x),
-            VariableValue::Instance(x, _) => write!(f, "[{} instance]", x.borrow().name),
+            VariableValue::Instance(x, vars) if x.borrow().tag.is_some() => {
+                // enum variants are shown the way they are built, as in `Rect(2, 3)`
+                let fields = x.borrow().fields();
+                if fields.is_empty() {
+                    return write!(f, "{}", x.borrow().name);
+                }
+                let vars = vars.borrow();
+                let values: Vec<String> = fields
+                    .iter()
+                    .map(|field| format!("{}", vars.get(field).unwrap_or(&VariableValue::Nil)))
+                    .collect();
+                write!(f, "{}({})", x.borrow().name, values.join(", "))
+            }
+            VariableValue::Instance(x, _) | VariableValue::Super(_, x, _) => write!(f, "[{} instance]", x.borrow().name),
             VariableValue::Type(x) => write!(f, "[{} type]", x.borrow().name),
             VariableValue::Tuple(x) => write!(
                 f,
@@ -38,6 +63,7 @@ impl<'a> fmt::Display for VariableValue<'a> {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            VariableValue::Range(x) => write!(f, "{}", x),
             VariableValue::Nil => write!(f, "nil"),
             VariableValue::Bail => write!(f, "bail"),
             VariableValue::Function(fun, _) => write!(f, "[function {}]", fun.get_name()),
@@ -90,7 +116,19 @@ impl<'a> PartialEq for VariableValue<'a> {
                     false
                 }
             }
-            VariableValue::Instance(_, _) => false, // instance comparison is not yet supported
+            VariableValue::Range(x) => {
+                if let VariableValue::Range(y) = other {
+                    x == y
+                } else {
+                    false
+                }
+            }
+            // enum variants are compared by value; comparison of other instances is not yet supported
+            VariableValue::Instance(x, x_vars) if x.borrow().tag.is_some() => match other {
+                VariableValue::Instance(y, y_vars) => Rc::ptr_eq(x, y) && *x_vars.borrow() == *y_vars.borrow(),
+                _ => false,
+            },
+            VariableValue::Instance(_, _) | VariableValue::Super(_, _, _) => false,
             VariableValue::Function(_, _) => false, // function comparison is not yet supported
             VariableValue::Type(_) => false,        // type comparison is not yet supported
         }
@@ -117,8 +155,8 @@ impl<'a> BinaryOp<'a, Self> for VariableValue<'a> {
                     VariableValue::Type(y) => {
                         x.binary_op(format!("[{} type]", y.borrow().name), op, loc)
                     }
-                    VariableValue::Instance(y, _) => {
-                        x.binary_op(format!("[{} instance]", y.borrow().name), op, loc)
+                    y @ (VariableValue::Instance(_, _) | VariableValue::Super(_, _, _) | VariableValue::Range(_)) => {
+                        x.binary_op(y.to_string(), op, loc)
                     }
                     _ => err_mixed_types(loc),
                 },
@@ -167,9 +205,9 @@ impl<'a> BinaryOp<'a, Self> for VariableValue<'a> {
                         err_invalid_op(loc)
                     }
                 }
-                VariableValue::Instance(x, _) => {
+                x @ (VariableValue::Instance(_, _) | VariableValue::Super(_, _, _) | VariableValue::Range(_)) => {
                     if let VariableValue::String(y) = b {
-                        format!("[{} instance]", x.borrow().name).binary_op(y, op, loc)
+                        x.to_string().binary_op(y, op, loc)
                     } else {
                         err_invalid_op(loc)
                     }
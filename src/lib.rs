@@ -5,6 +5,7 @@ pub mod location;
 pub mod parser;
 pub mod test;
 pub mod internal;
+pub mod vm;
 
 pub use location::Location;
 
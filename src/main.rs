@@ -1,6 +1,7 @@
 use std::env;
 use std::fs;
 use std::process;
+use std::thread;
 pub mod ast;
 pub mod error;
 pub mod interpreter;
@@ -8,16 +9,43 @@ pub mod location;
 pub mod parser;
 pub mod test;
 pub mod internal;
+pub mod vm;
 
 use ast::resolve;
 pub use location::Location;
 
+/// Native stack space reserved for each PatPat call, which nests several calls of the interpreter
+const STACK_SIZE_PER_CALL: usize = 64 * 1024;
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() <= 1 {
         exit_with_style("Invalid number of arguments");
     }
 
+    let max_depth = match args.iter().position(|x| *x == String::from("--max-depth")) {
+        Some(index) => match args.get(index + 1).and_then(|x| x.parse::<usize>().ok()) {
+            Some(depth) => depth,
+            None => exit_with_style("Expected a number after --max-depth"),
+        },
+        None => interpreter::DEFAULT_MAX_CALL_DEPTH,
+    };
+
+    // the interpreter runs in its own thread, as to have enough stack space for `max_depth` calls
+    let child = thread::Builder::new()
+        .stack_size((max_depth + 1) * STACK_SIZE_PER_CALL)
+        .spawn(move || run(args, max_depth))
+        .unwrap_or_else(|e| {
+            exit_with_style(&format!("Couldn't allocate enough stack space for --max-depth {}: {}", max_depth, e))
+        });
+    if child.join().is_err() {
+        process::exit(101);
+    }
+}
+
+fn run(args: Vec<String>, max_depth: usize) {
+    interpreter::MAX_CALL_DEPTH.with(|d| *d.borrow_mut() = max_depth);
+
     let raw = match fs::read_to_string(&args[1]) {
         Ok(v) => v,
         Err(e) => {
@@ -52,10 +80,14 @@ fn main() {
         println!("{:#?}", resolved.borrow());
     }
 
-    interpreter::interprete(resolved, Vec::new());
+    if let Some(_) = args.iter().find(|x| **x == String::from("--vm")) {
+        vm::run(resolved);
+    } else {
+        interpreter::interprete(resolved, Vec::new());
+    }
 }
 
-fn exit_with_style(msg: &str) {
+fn exit_with_style(msg: &str) -> ! {
     // have ~style~
     eprintln!("{}", msg);
     process::exit(1);
@@ -0,0 +1,57 @@
+//! Skips block comments (`/* ... */`), which may span several lines and be nested.
+
+use super::{CompError, CompLocation};
+use crate::SrcFile;
+
+/** Skips the rest of a block comment, whose opening delimiter has just been consumed.
+    @param file - The current source file
+    @param lines - The lines of the source file
+    @param line_index - The current line of code; it is moved to the line where the comment ends
+    @param char_index - The current char within that line of code
+    @param trimmed_line - The trimmed line, starting right after the opening delimiter
+
+    Block comments nest, so that commenting out code which already contains a block comment works as expected.
+**/
+pub fn skip_block_comment<'a>(
+    file: &'a SrcFile,
+    lines: &[&'a str],
+    line_index: &mut usize,
+    char_index: &mut usize,
+    trimmed_line: &mut &'a str,
+) {
+    let start = (*line_index, *char_index - 2);
+    let mut depth = 1usize;
+    loop {
+        let line: &'a str = trimmed_line;
+        let mut pos = 0usize;
+        while pos < line.len() {
+            let rest = &line[pos..];
+            if rest.starts_with("/*") {
+                depth += 1;
+                pos += 2;
+            } else if rest.starts_with("*/") {
+                depth -= 1;
+                pos += 2;
+                if depth == 0 {
+                    *char_index += pos;
+                    *trimmed_line = &line[pos..];
+                    return;
+                }
+            } else {
+                pos += rest.chars().next().unwrap().len_utf8();
+            }
+        }
+
+        if *line_index + 1 >= lines.len() {
+            CompError::new(
+                23,
+                String::from("Unterminated block comment: expected a '*/'"),
+                CompLocation::Char(&file.contents, start.0, start.1),
+            )
+            .print_and_exit();
+        }
+        *line_index += 1;
+        *char_index = 0;
+        *trimmed_line = lines[*line_index];
+    }
+}
@@ -1,8 +1,8 @@
 use super::{
-    ast::{DefineMember, ExprTerm, Expression},
-    construct, construct_non_expression,
-    token::Operator,
-    tuple, ASTKind, ASTNode, Token, TokenTree, AST,
+    ast::{DefineMember, Destructure, ExprTerm, Expression},
+    construct, construct_non_expression, functions,
+    token::{Operator, StringPart},
+    ASTKind, ASTNode, Token, TokenTree, AST,
 };
 use crate::{
     error::{CompError, CompLocation},
@@ -54,6 +54,44 @@ pub fn construct_expression<'a>(
         }
     }
 
+    // Handle destructuring definitions, ie. `(a, b): (b, a)`
+    if let (Some((ASTNode::Tuple(_, false), _)), Some((Token::CompoundDefine(_), loc))) =
+        (&first_term, tree.tokens.get(offset2))
+    {
+        CompError::new(
+            108,
+            String::from("Cannot destructure with a compound definition: use `:` instead"),
+            CompLocation::from(loc),
+        )
+        .print_and_exit();
+    }
+    if let (Some((ASTNode::Tuple(_, false), _)), Some((Token::Define, define_loc))) =
+        (&first_term, tree.tokens.get(offset2))
+    {
+        if first_term_ops.is_empty() {
+            if tree.tokens.len() == offset2 + 1 {
+                CompError::new(
+                    19,
+                    String::from("Incomplete variable definition: expected expression or value"),
+                    CompLocation::from(define_loc),
+                )
+                .print_and_exit()
+            }
+            let (tuple, loc) = first_term.unwrap();
+            let target = Destructure::from_node(&tuple, &loc);
+            *offset = offset2 + 1;
+            let value = construct(tree, offset).unwrap_or_else(|| unimplemented!());
+            return Some((
+                ASTNode::ComplexDef(
+                    Expression { terms: Vec::new() },
+                    DefineMember::Tuple(target),
+                    Box::new(value.0),
+                ),
+                loc,
+            ));
+        }
+    }
+
     if tree.tokens.len() > offset2 {
         // check if we're not at the end of the token list
         if let (Token::Operator(main_op), main_loc) = tree.tokens[offset2].clone() {
@@ -111,7 +149,9 @@ pub fn construct_expression<'a>(
 
                     // Handle <expresssion> <define> <expression>
                     if tree.tokens.len() > offset2 + 1 {
-                        if let (Token::Define, _) = &tree.tokens[offset2 + 1] {
+                        if let (Token::Define | Token::CompoundDefine(_), _) =
+                            &tree.tokens[offset2 + 1]
+                        {
                             if term_ops.len() > 0 {
                                 CompError::new(
                                     108,
@@ -178,7 +218,15 @@ pub fn construct_expression<'a>(
                                 }
                             }
                             (ASTNode::Tuple(ast, is_partial), tuple_loc) => {
-                                res = (ASTNode::Tuple(ast, is_partial), tuple_loc);
+                                // Handle named arguments, ie. `fn.(a, b: 2)`
+                                res = (
+                                    ASTNode::Tuple(functions::call_args(ast), is_partial),
+                                    tuple_loc,
+                                );
+                            }
+                            (ASTNode::VariableDef(name, value), def_loc) => {
+                                // Handle a single named argument, ie. `fn.(b: 2)`
+                                res = node_into_tuple(ASTNode::NamedArg(name, value), def_loc);
                             }
                             (ASTNode::Nil, tuple_loc) => {
                                 res = (ASTNode::Nil, tuple_loc);
@@ -247,6 +295,40 @@ pub fn construct_expression<'a>(
     None
 }
 
+pub fn construct_interpolation<'a>(
+    parts: Vec<StringPart<'a>>,
+    loc: Location<'a>,
+) -> ASTNode<'a> {
+    //! Constructs an interpolated string, ie. `"x = {x}"`, whose parts are joined once converted to strings, whatever their type
+    let mut nodes: Vec<(ASTNode<'a>, Location<'a>)> = Vec::new();
+    for part in parts.into_iter() {
+        match part {
+            StringPart::Literal(string) => nodes.push((ASTNode::String(string), loc.clone())),
+            StringPart::Expression(tree) => {
+                let tree = Rc::new(tree);
+                let mut offset = 0usize;
+                let term = if tree.tokens.is_empty() {
+                    None
+                } else {
+                    construct(tree.clone(), &mut offset)
+                };
+                match term {
+                    Some(term) if offset == tree.tokens.len() => nodes.push(term),
+                    _ => CompError::new(
+                        110,
+                        String::from("Invalid string interpolation: expected a single expression"),
+                        CompLocation::from(
+                            tree.tokens.get(offset).map_or(&tree.start_loc, |token| &token.1),
+                        ),
+                    )
+                    .print_and_exit(),
+                }
+            }
+        }
+    }
+    ASTNode::Interpolation(nodes)
+}
+
 fn append_term<'a, 'b>(
     terms: &'b mut Vec<ExprTerm<'a>>,
     term: Option<(ASTNode<'a>, Location<'a>)>,
@@ -365,7 +447,7 @@ fn handle_definition<'a>(
 ) -> Option<(ASTNode<'a>, Location<'a>)> {
     /*! Handles complex definitions, ie. <expr> <define> <expr>
     Called from `construct_expression`.
-    Checks that the operator is a valid operator (MemberAccessor), that the member assigned is a valid member (variable, number or tuple) and returns a ComplexDef.
+    Checks that the operator is a valid operator (MemberAccessor), that the member assigned is a valid member (variable or number) and returns a ComplexDef.
     */
     if let Operator::MemberAccessor = op {
         let arg: DefineMember;
@@ -378,18 +460,19 @@ fn handle_definition<'a>(
                 *offset += 1;
                 arg = DefineMember::Number(*num);
             }
-            (Token::Tuple(_), _) => {
-                arg = DefineMember::Tuple(Box::new(
-                    tuple::construct_tuple(tree.clone(), offset)
-                        .unwrap_or_else(|| panic!("Error while parsing tuple"))
-                        .0,
-                ));
+            (Token::Tuple(_), loc) => {
+                CompError::new(
+                    108,
+                    String::from("Cannot define several members at once: destructure into variables instead, ie. `(a, b): value`"),
+                    CompLocation::from(loc),
+                )
+                .print_and_exit();
             }
             (_, loc) => {
                 CompError::new(
                     108,
                     String::from(
-                        "Invalid expression term to define: expected string, number or tuple",
+                        "Invalid expression term to define: expected a name or a number",
                     ),
                     CompLocation::from(loc),
                 )
@@ -397,12 +480,16 @@ fn handle_definition<'a>(
             }
         }
 
+        let op = match &tree.tokens[*offset] {
+            (Token::CompoundDefine(op), _) => Some(*op),
+            _ => None,
+        };
         *offset += 1; // <define>
 
         let value = construct(tree, offset).unwrap_or_else(|| unimplemented!());
 
         Some((
-            ASTNode::ComplexDef(Expression { terms }, arg, Box::new(value.0)),
+            ASTNode::ComplexDef(Expression { terms }, arg, Box::new(value.0)).compound(op),
             loc,
         ))
     } else {
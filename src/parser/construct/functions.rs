@@ -35,8 +35,9 @@ pub fn construct_pattern_declaration<'a>(
                     *offset += 5;
                     Some((
                         ASTNode::PatternDecl(ast::Pattern {
-                            function: f,
+                            function: Box::new(f),
                             name: name.to_string(),
+                            doc: None,
                         }),
                         location,
                     ))
@@ -52,14 +53,20 @@ pub fn construct_pattern_call<'a>(
     tree: Rc<TokenTree<'a>>,
     offset: &mut usize,
 ) -> Option<(ASTNode<'a>, Location<'a>)> {
-    /*! Handles construct calls, ie `'pattern(...)` */
+    /*! Handles construct calls, ie `'pattern(...)`; the arms of `#match(...)` are parsed by `ast::Match::parse` */
     // PATTERN_CALL = PATTERN, {whitespace}, TUPLE;
     if let Token::Pattern(name) = &tree.tokens[*offset].0 {
         if tree.tokens.len() == *offset + 1 {
             return None;
         }
         if let Token::Tuple(t) = &tree.tokens[*offset + 1].0 {
-            let args = AST::parse(t.clone(), ASTKind::Tuple);
+            if name == "#match" {
+                let location = tree.tokens[*offset].1.clone();
+                let node = ASTNode::Match(Box::new(ast::Match::parse(t.clone(), &location)));
+                *offset += 2;
+                return Some((node, location));
+            }
+            let args = call_args(AST::parse(t.clone(), ASTKind::Tuple));
             let location = tree.tokens[*offset].1.clone();
             *offset += 2;
             return Some((ASTNode::PatternCall(name.to_string(), args), location));
@@ -68,6 +75,20 @@ pub fn construct_pattern_call<'a>(
     None
 }
 
+pub fn call_args(mut args: AST<'_>) -> AST<'_> {
+    /*! Turns the `name: value` terms of a call's argument tuple into named arguments.
+     * These would otherwise be parsed as variable definitions.
+     */
+    for (node, _) in args.instructions.iter_mut() {
+        if let ASTNode::VariableDef(_, _) = node {
+            if let ASTNode::VariableDef(name, value) = std::mem::replace(node, ASTNode::Nil) {
+                *node = ASTNode::NamedArg(name, value);
+            }
+        }
+    }
+    args
+}
+
 pub fn construct_standalone_function<'a>(
     tree: Rc<TokenTree<'a>>,
     offset: &mut usize,
@@ -86,7 +107,7 @@ pub fn construct_standalone_function<'a>(
         )?;
         let location = tree.tokens[*offset].1.clone();
         *offset += 3;
-        Some((ASTNode::Function(res), location))
+        Some((ASTNode::Function(Box::new(res)), location))
     }
 }
 
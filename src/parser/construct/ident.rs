@@ -1,4 +1,4 @@
-use super::{ASTNode, Token, TokenTree};
+use super::{expr, ASTNode, Token, TokenTree};
 use crate::Location;
 use std::rc::Rc;
 
@@ -11,13 +11,17 @@ pub fn construct_ident<'a>(
     /*!
     Tries to construct simple terms:
     - numbers
-    - strings
+    - strings, including interpolated ones
     - booleans
     */
     let res = match &tree.tokens[*offset] {
         (Token::Boolean(b), loc) => Some((ASTNode::Boolean(*b), loc.clone())),
         (Token::Number(n), loc) => Some((ASTNode::Number(*n), loc.clone())),
         (Token::String(s), loc) => Some((ASTNode::String(s.clone()), loc.clone())),
+        (Token::InterpolatedString(parts), loc) => Some((
+            expr::construct_interpolation(parts.clone(), loc.clone()),
+            loc.clone(),
+        )),
         (Token::TypeName(n), loc) => Some((ASTNode::TypeName(n.clone()), loc.clone())),
         (Token::VoidSymbol, loc) => Some((ASTNode::VoidSymbol, loc.clone())),
         _ => None,
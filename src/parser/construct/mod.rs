@@ -40,9 +40,12 @@ pub fn construct_non_expression<'a>(
         .or_else(|| functions::construct_standalone_function(tree.clone(), offset))
         .or_else(|| functions::construct_standalone_pattern(tree.clone(), offset))
         .or_else(|| r#struct::construct_struct(tree.clone(), offset))
+        .or_else(|| r#struct::construct_struct_literal(tree.clone(), offset))
+        .or_else(|| r#struct::construct_extension(tree.clone(), offset))
         .or_else(|| variables::construct_variable_definition(tree.clone(), offset))
         .or_else(|| variables::construct_variable(tree.clone(), offset))
         .or_else(|| variables::construct_variable_declaration(tree.clone(), offset))
+        .or_else(|| variables::construct_constant(tree.clone(), offset))
         .or_else(|| tuple::construct_tuple(tree.clone(), offset))
         .or_else(|| ident::construct_ident(tree.clone(), offset))
         .or_else(|| block::construct_block(tree.clone(), offset))
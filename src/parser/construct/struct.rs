@@ -1,4 +1,6 @@
-use super::{ASTKind, ASTNode, Token, TokenTree, AST};
+use super::ast::{EnumDef, InterfaceDef, InterfaceMember, StructDef, VariantDef};
+use super::{functions, ASTKind, ASTNode, Token, TokenTree, AST};
+use crate::parser::token::TypeName;
 use crate::error::*;
 use crate::Location;
 use std::rc::Rc;
@@ -9,7 +11,7 @@ pub fn construct_struct<'a>(
     tree: Rc<TokenTree<'a>>,
     offset: &mut usize,
 ) -> Option<(ASTNode<'a>, Location<'a>)> {
-    /*! Constructs Struct definitions
+    /*! Constructs Struct, Interface and Enum definitions
      */
     if tree.tokens.len() > *offset + 1 {
         if let (Token::TypeName(tn), tn_loc) = &tree.tokens[*offset] {
@@ -22,32 +24,257 @@ pub fn construct_struct<'a>(
                     )
                     .print_and_exit();
                 }
-                if let (Token::Struct, _) = &tree.tokens[*offset + 2] {
-                    if let (Token::Block(tree), _) = &tree.tokens[*offset + 3] {
+                match &tree.tokens[*offset + 2] {
+                    (Token::Struct, _) => {
+                        // `struct extends Parent implements Interface, ... {...}`
+                        let mut block_offset = *offset + 3;
+                        let mut parent = None;
+                        let mut interfaces = Vec::new();
+                        if let (Token::Extends, extends_loc) = &tree.tokens[block_offset] {
+                            parent = Some(expect_type_name(&tree, block_offset + 1, "extends", extends_loc).0);
+                            block_offset += 2;
+                        }
+                        if let Some((Token::Implements, implements_loc)) = tree.tokens.get(block_offset) {
+                            interfaces.push(expect_type_name(&tree, block_offset + 1, "implements", implements_loc));
+                            block_offset += 2;
+                            while let Some((Token::Separator, separator_loc)) = tree.tokens.get(block_offset) {
+                                interfaces.push(expect_type_name(&tree, block_offset + 1, ",", separator_loc));
+                                block_offset += 2;
+                            }
+                        }
+                        let body = expect_block(&tree, block_offset);
+                        *offset = block_offset + 1;
+                        Some((
+                            ASTNode::Struct(StructDef {
+                                name: tn.clone(),
+                                parent,
+                                interfaces,
+                                body: AST::parse(body, ASTKind::Struct),
+                                doc: None,
+                            }),
+                            tn_loc.clone(),
+                        ))
+                    }
+                    (Token::Interface, _) => {
+                        let body = expect_block(&tree, *offset + 3);
                         *offset += 4;
-                        return Some((
-                            ASTNode::Struct(tn.clone(), AST::parse(tree.clone(), ASTKind::Struct)),
+                        Some((
+                            ASTNode::Interface(InterfaceDef {
+                                name: tn.clone(),
+                                members: construct_interface_members(&body),
+                                doc: None,
+                            }),
                             tn_loc.clone(),
-                        ));
-                    } else {
-                        CompError::new(
+                        ))
+                    }
+                    (Token::Enum, _) => {
+                        let body = expect_block(&tree, *offset + 3);
+                        *offset += 4;
+                        Some((
+                            ASTNode::Enum(EnumDef {
+                                name: tn.clone(),
+                                variants: construct_variants(&body),
+                                doc: None,
+                            }),
+                            tn_loc.clone(),
+                        ))
+                    }
+                    (_, loc) => CompError::new(
+                        20,
+                        String::from("Unexpected token in struct definition: expected `struct`, `interface` or `enum`"),
+                        CompLocation::from(loc),
+                    )
+                    .print_and_exit(),
+                }
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+pub fn construct_extension<'a>(
+    tree: Rc<TokenTree<'a>>,
+    offset: &mut usize,
+) -> Option<(ASTNode<'a>, Location<'a>)> {
+    /*! Constructs extensions of primitive types: `extends String {...}`, whose body may only declare patterns
+     */
+    if let Some((Token::Extends, extends_loc)) = tree.tokens.get(*offset) {
+        let (tn, tn_loc) = expect_type_name(&tree, *offset + 1, "extends", extends_loc);
+        let body = AST::parse(expect_block(&tree, *offset + 2), ASTKind::Struct);
+        for (node, loc) in body.instructions.iter() {
+            if !matches!(node, ASTNode::PatternDecl(_)) {
+                CompError::new(
+                    20,
+                    format!("Invalid term in extension of {}: only patterns may be declared", tn.name),
+                    CompLocation::from(loc),
+                )
+                .print_and_exit();
+            }
+        }
+        *offset += 3;
+        Some((ASTNode::Extension(tn, body), tn_loc))
+    } else {
+        None
+    }
+}
+
+fn expect_type_name<'a>(
+    tree: &TokenTree<'a>,
+    index: usize,
+    after: &str,
+    loc: &Location<'a>,
+) -> (TypeName, Location<'a>) {
+    //! Returns the type name at `index`, which must follow the keyword `after`
+    match tree.tokens.get(index) {
+        Some((Token::TypeName(tn), tn_loc)) => (tn.clone(), tn_loc.clone()),
+        _ => CompError::new(
+            20,
+            format!("Unexpected token in struct definition: expected a type name following `{}`", after),
+            CompLocation::from(loc),
+        )
+        .print_and_exit(),
+    }
+}
+
+fn expect_block<'a>(tree: &TokenTree<'a>, index: usize) -> TokenTree<'a> {
+    //! Returns the body of the struct or interface, at `index`
+    match tree.tokens.get(index) {
+        Some((Token::Block(body), _)) => body.clone(),
+        _ => CompError::new(
+            20,
+            String::from("Unexpected token in struct definition: expected struct block"),
+            CompLocation::from(&tree.tokens[index - 1].1),
+        )
+        .print_and_exit(),
+    }
+}
+
+fn construct_interface_members<'a>(body: &TokenTree<'a>) -> Vec<InterfaceMember<'a>> {
+    /*! Constructs the members of an interface, which are either variable declarations (`let name`) or pattern signatures (`'name: (#self(), x)`)
+     */
+    let mut members = Vec::new();
+    let mut offset = 0;
+    while offset < body.tokens.len() {
+        match &body.tokens[offset..] {
+            [(Token::Separator, _), ..] | [(Token::DocComment(_), _), ..] => offset += 1,
+            [(Token::Let, loc), (Token::Symbol(name), _), ..] => {
+                members.push(InterfaceMember::Variable(name.clone(), loc.clone()));
+                offset += 2;
+            }
+            [(Token::Pattern(name), loc), (Token::Define, _), (Token::Tuple(args), _), ..] => {
+                let args = AST::parse(args.clone(), ASTKind::ArgTuple);
+                let arity = args
+                    .instructions
+                    .iter()
+                    .filter(|(arg, _)| {
+                        matches!(
+                            arg,
+                            ASTNode::Variable(_) | ASTNode::TypedVariable(_, _) | ASTNode::VariableDef(_, _) | ASTNode::Tuple(_, false)
+                        )
+                    })
+                    .count();
+                members.push(InterfaceMember::Pattern(name.clone(), arity, loc.clone()));
+                offset += 3;
+            }
+            [(_, loc), ..] => CompError::new(
+                20,
+                String::from("Invalid term in interface definition: expected `let name` or `'pattern: (args)`"),
+                CompLocation::from(loc),
+            )
+            .print_and_exit(),
+            [] => unreachable!(),
+        }
+    }
+    members
+}
+
+fn construct_variants<'a>(body: &TokenTree<'a>) -> Vec<VariantDef<'a>> {
+    /*! Constructs the variants of an enum, which are either bare (`Idle`) or carry a payload (`Circle(radius)`)
+     */
+    let mut variants = Vec::new();
+    let mut offset = 0;
+    while offset < body.tokens.len() {
+        match &body.tokens[offset..] {
+            [(Token::Separator, _), ..] | [(Token::DocComment(_), _), ..] => offset += 1,
+            [(Token::TypeName(name), loc), (Token::Tuple(payload), _), ..] => {
+                let mut fields = Vec::new();
+                for (token, field_loc) in payload.tokens.iter() {
+                    match token {
+                        Token::Symbol(field) => fields.push(field.clone()),
+                        Token::Separator => {}
+                        _ => CompError::new(
                             20,
-                            String::from(
-                                "Unexpected token in struct definition: expected struct block",
-                            ),
-                            CompLocation::from(&tree.tokens[*offset + 2].1),
+                            String::from("Invalid term in enum variant: expected field names"),
+                            CompLocation::from(field_loc),
                         )
-                        .print_and_exit();
+                        .print_and_exit(),
                     }
-                } else {
+                }
+                variants.push(VariantDef {
+                    name: name.clone(),
+                    fields,
+                    location: loc.clone(),
+                });
+                offset += 2;
+            }
+            [(Token::TypeName(name), loc), ..] => {
+                variants.push(VariantDef {
+                    name: name.clone(),
+                    fields: Vec::new(),
+                    location: loc.clone(),
+                });
+                offset += 1;
+            }
+            [(_, loc), ..] => CompError::new(
+                20,
+                String::from("Invalid term in enum definition: expected `Variant` or `Variant(fields)`"),
+                CompLocation::from(loc),
+            )
+            .print_and_exit(),
+            [] => unreachable!(),
+        }
+    }
+    variants
+}
+
+pub fn construct_struct_literal<'a>(
+    tree: Rc<TokenTree<'a>>,
+    offset: &mut usize,
+) -> Option<(ASTNode<'a>, Location<'a>)> {
+    /*! Constructs struct literals, ie. `Point {x: 1, y: 2}`, and enum variants, ie. `Circle(2)`; the block or tuple must start on the same line as the type name.
+     * The fields of a variant may be given in order; the resolver maps them to their names.
+     */
+    if let (Token::TypeName(tn), tn_loc) = &tree.tokens[*offset] {
+        if let Some((Token::Block(block), block_loc)) = tree.tokens.get(*offset + 1) {
+            if block_loc.line != tn_loc.line {
+                return None;
+            }
+            let fields = functions::call_args(AST::parse(block.clone(), ASTKind::Tuple));
+            for (node, loc) in fields.instructions.iter() {
+                if !matches!(node, ASTNode::NamedArg(_, _)) {
                     CompError::new(
                         20,
-                        String::from("Unexpected token in struct definition: expected `struct`"),
-                        CompLocation::from(&tree.tokens[*offset + 2].1),
+                        String::from("Invalid term in struct literal: expected `field: value`"),
+                        CompLocation::from(loc),
                     )
                     .print_and_exit();
                 }
             }
+            *offset += 2;
+            return Some((ASTNode::StructLiteral(tn.clone(), fields), tn_loc.clone()));
+        }
+        if let Some((Token::Tuple(tuple), tuple_loc)) = tree.tokens.get(*offset + 1) {
+            if tuple_loc.line != tn_loc.line {
+                return None;
+            }
+            let fields = functions::call_args(AST::parse(tuple.clone(), ASTKind::Tuple));
+            *offset += 2;
+            return Some((ASTNode::StructLiteral(tn.clone(), fields), tn_loc.clone()));
         }
     }
     None
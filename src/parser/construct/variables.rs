@@ -1,4 +1,4 @@
-use super::{construct, ASTNode, Token, TokenTree};
+use super::{ast::Destructure, construct, tuple, ASTNode, Token, TokenTree};
 use crate::{error::*, Location};
 use std::rc::Rc;
 
@@ -37,6 +37,34 @@ pub fn construct_variable_declaration<'a>(
             )
             .print_and_exit();
         }
+        if let (Token::Tuple(_), _) = &tree.tokens[*offset + 1] {
+            // destructuring declaration, ie. `let (a, b): value`
+            let mut offset2 = *offset + 1;
+            let (target, target_loc) = tuple::construct_tuple(tree.clone(), &mut offset2)
+                .unwrap_or_else(|| panic!("Error while parsing tuple"));
+            let target = Destructure::from_node(&target, &target_loc);
+            if !matches!(tree.tokens.get(offset2), Some((Token::Define, _)))
+                || tree.tokens.len() == offset2 + 1
+            {
+                CompError::new(
+                    16,
+                    String::from("Incomplete variable declaration: destructuring declarations require a value"),
+                    CompLocation::from(loc),
+                )
+                .print_and_exit();
+            }
+            *offset = offset2 + 1;
+            let expr = construct(tree.clone(), offset).unwrap_or_else(|| panic!("Unimplemented"));
+            if !expr.0.is_valid_expr_term() {
+                CompError::new(
+                    19,
+                    String::from("Invalid term in variable definition"),
+                    CompLocation::from(expr.1),
+                )
+                .print_and_exit();
+            }
+            return Some((ASTNode::DestructureInit(target, Box::new(expr.0)), loc.clone()));
+        }
         if let (Token::Symbol(symbol), _) = &tree.tokens[*offset + 1] {
             if tree.tokens.len() > *offset + 2 {
                 if let (Token::Define, loc3) = &tree.tokens[*offset + 2] {
@@ -68,7 +96,7 @@ pub fn construct_variable_declaration<'a>(
                     }
 
                     return Some((
-                        ASTNode::VariableInit(symbol.clone(), Box::new(expr.0)),
+                        ASTNode::VariableInit(symbol.clone(), Box::new(expr.0), None),
                         loc.clone(),
                     ));
                 }
@@ -88,42 +116,94 @@ pub fn construct_variable_declaration<'a>(
     None
 }
 
+pub fn construct_constant<'a>(
+    tree: Rc<TokenTree<'a>>,
+    offset: &mut usize,
+) -> Option<(ASTNode<'a>, Location<'a>)> {
+    /*! Matches constant declarations, ie. `const name: value`, and the static members of structs, ie. `static name: value`; both require a value */
+    let (is_static, loc) = match &tree.tokens[*offset] {
+        (Token::Const, loc) => (false, loc),
+        (Token::Static, loc) => (true, loc),
+        _ => return None,
+    };
+    let (keyword, what) = if is_static { ("static", "static member") } else { ("const", "constant") };
+    match &tree.tokens[*offset + 1..] {
+        [(Token::Symbol(symbol), _), (Token::Define, _), _, ..] => {
+            *offset += 3;
+            let expr =
+                construct(tree.clone(), offset).unwrap_or_else(|| panic!("Unimplemented"));
+
+            if !expr.0.is_valid_expr_term() {
+                CompError::new(
+                    19,
+                    format!("Invalid term in {} definition", what),
+                    CompLocation::from(expr.1),
+                )
+                .print_and_exit();
+            }
+
+            let node = if is_static {
+                ASTNode::StaticInit(symbol.clone(), Box::new(expr.0), None)
+            } else {
+                ASTNode::ConstInit(symbol.clone(), Box::new(expr.0), None)
+            };
+            Some((node, loc.clone()))
+        }
+        [(Token::Symbol(_), _), ..] | [] => {
+            CompError::new(
+                16,
+                format!("Incomplete {} declaration: {}s require a value, ie. `{} name: value`", what, what, keyword),
+                CompLocation::from(loc),
+            )
+            .print_and_exit();
+        }
+        [(_, loc2), ..] => {
+            CompError::new(
+                17,
+                format!("Invalid term in {} declaration", what),
+                CompLocation::from(loc2),
+            )
+            .print_and_exit();
+        }
+    }
+}
+
 pub fn construct_variable_definition<'a>(
     tree: Rc<TokenTree<'a>>,
     offset: &mut usize,
 ) -> Option<(ASTNode<'a>, Location<'a>)> {
     if tree.tokens.len() > *offset + 1 {
-        if let (Token::Define, define_loc) = &tree.tokens[*offset + 1] {
-            if let (Token::Symbol(symbol), sym_loc) = &tree.tokens[*offset] {
-                if tree.tokens.len() == *offset + 2 {
-                    CompError::new(
-                        19,
-                        String::from(
-                            "Incomplete variable definition: expected expression or value",
-                        ),
-                        CompLocation::from(define_loc),
-                    )
-                    .print_and_exit()
-                }
-
-                *offset += 2;
-                let expr =
-                    construct(tree.clone(), offset).unwrap_or_else(|| panic!("Unimplemented"));
+        let (op, define_loc) = match &tree.tokens[*offset + 1] {
+            (Token::Define, loc) => (None, loc),
+            (Token::CompoundDefine(op), loc) => (Some(*op), loc),
+            _ => return None,
+        };
+        if let (Token::Symbol(symbol), sym_loc) = &tree.tokens[*offset] {
+            if tree.tokens.len() == *offset + 2 {
+                CompError::new(
+                    19,
+                    String::from("Incomplete variable definition: expected expression or value"),
+                    CompLocation::from(define_loc),
+                )
+                .print_and_exit()
+            }
 
-                if !expr.0.is_valid_expr_term() {
-                    CompError::new(
-                        19,
-                        String::from("Invalid term in variable definition"),
-                        CompLocation::from(expr.1),
-                    )
-                    .print_and_exit();
-                }
+            *offset += 2;
+            let expr = construct(tree.clone(), offset).unwrap_or_else(|| panic!("Unimplemented"));
 
-                return Some((
-                    ASTNode::VariableDef(symbol.clone(), Box::new(expr.0)),
-                    sym_loc.clone(),
-                ));
+            if !expr.0.is_valid_expr_term() {
+                CompError::new(
+                    19,
+                    String::from("Invalid term in variable definition"),
+                    CompLocation::from(expr.1),
+                )
+                .print_and_exit();
             }
+
+            return Some((
+                ASTNode::VariableDef(symbol.clone(), Box::new(expr.0)).compound(op),
+                sym_loc.clone(),
+            ));
         }
     }
     None
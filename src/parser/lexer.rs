@@ -0,0 +1,198 @@
+//! Hand-written tokenizer, which recognizes the terms of a line of source code in a single pass.
+//! Terms are tried in the following order of precedence, the first one matching the start of the line winning:
+//!
+//! | Kind            | Grammar                                   |
+//! |-----------------|-------------------------------------------|
+//! | Boolean         | `true` or `false`, as whole words         |
+//! | Let, Const, ... | `let`, `const`, `static`, `struct`, as whole words |
+//! | Extends, ...    | `extends`, `interface`, `implements`, `enum`, as whole words |
+//! | Use, Load       | `#use`, `#load`, as whole words           |
+//! | Symbol          | `_?[a-z][a-z_\d]*`                        |
+//! | Define          | `:`                                       |
+//! | CompoundDefine  | `+:`, `-:`, `*:`, `/:`, `%:`              |
+//! | Space           | `\s+`                                     |
+//! | DocComment      | `///` up to the end of the line           |
+//! | Comment         | `//`                                      |
+//! | BlockComment    | `/*`                                      |
+//! | Pattern         | `['#]\w(?:\w\|::)*`                       |
+//! | TupleStart, End | `(`, `)`                                  |
+//! | Number          | `-?\d[\w.]*`, with signed exponents (validated by `number`); a trailing `.` before a method call is left out |
+//! | Arrow           | `=>`                                      |
+//! | Type            | `<\s*[!~]?\s*[A-Z]\w*\s*>`                  |
+//! | Operator        | `->`, `.`, `>>`, `>=`, `<=`, `==`, `!=`, `&&`, `\|\|`, `\|>`, `!`, `+`, `-`, `/`, `*`, `<`, `>`, `%`, `~` |
+//! | TypeName        | `[A-Z]\w*`                                |
+//! | BlockStart, End | `{`, `}`                                  |
+//! | StringDelimiter | `"`                                       |
+//! | Separator       | `,`                                       |
+//! | VoidSymbol      | `_`                                       |
+
+use super::token::Kind;
+
+/// A term at the start of a line, as recognized by `next_term`
+#[derive(Debug, Clone, Copy)]
+pub struct Term<'b> {
+    pub kind: Kind,
+    /// The source code of the term, whose length is the number of bytes to skip
+    pub text: &'b str,
+}
+
+const KEYWORDS: [(&str, Kind); 12] = [
+    ("true", Kind::Boolean),
+    ("false", Kind::Boolean),
+    ("let", Kind::Let),
+    ("const", Kind::Const),
+    ("static", Kind::Static),
+    ("struct", Kind::Struct),
+    ("extends", Kind::Extends),
+    ("interface", Kind::Interface),
+    ("implements", Kind::Implements),
+    ("enum", Kind::Enum),
+    ("#use", Kind::Use),
+    ("#load", Kind::Load),
+];
+
+const OPERATORS: [&str; 19] = [
+    "->", ".", ">>", ">=", "<=", "==", "!=", "&&", "||", "|>", "!", "+", "-", "/", "*", "<", ">", "%",
+    "~",
+];
+
+/** Recognizes the term at the start of `line`, returning `None` if there is none.
+    The term is never empty, so that the caller may skip it and recognize the next one.
+**/
+pub fn next_term(line: &str) -> Option<Term<'_>> {
+    let first = line.chars().next()?;
+    let second = line[first.len_utf8()..].chars().next();
+
+    let term = |kind: Kind, length: usize| Some(Term { kind, text: &line[..length] });
+
+    for (keyword, kind) in KEYWORDS.iter() {
+        if line.starts_with(keyword) && !line[keyword.len()..].starts_with(is_word) {
+            return term(*kind, keyword.len());
+        }
+    }
+
+    match first {
+        'a'..='z' => term(Kind::Symbol, 1 + scan(&line[1..], is_symbol)),
+        '_' if matches!(second, Some('a'..='z')) => {
+            term(Kind::Symbol, 2 + scan(&line[2..], is_symbol))
+        }
+        ':' => term(Kind::Define, 1),
+        '+' | '-' | '*' | '/' | '%' if second == Some(':') => term(Kind::CompoundDefine, 2),
+        c if c.is_whitespace() => term(Kind::Space, scan(line, char::is_whitespace)),
+        '/' if line.starts_with("///") && !line.starts_with("////") => {
+            term(Kind::DocComment, line.len())
+        }
+        '/' if second == Some('/') => term(Kind::Comment, 2),
+        '/' if second == Some('*') => term(Kind::BlockComment, 2),
+        '\'' | '#' if second.is_some_and(is_word) => {
+            term(Kind::Pattern, 1 + scan_pattern(&line[1..]))
+        }
+        '(' => term(Kind::TupleStart, 1),
+        ')' => term(Kind::TupleEnd, 1),
+        c if c.is_ascii_digit() => term(Kind::Number, scan_number(line)),
+        '-' if second.is_some_and(|c| c.is_ascii_digit()) => {
+            term(Kind::Number, 1 + scan_number(&line[1..]))
+        }
+        '=' if second == Some('>') => term(Kind::Arrow, 2),
+        _ => {
+            if let Some(length) = scan_type(line) {
+                return term(Kind::Type, length);
+            }
+            if let Some(operator) = OPERATORS.iter().find(|op| line.starts_with(*op)) {
+                return term(Kind::Operator, operator.len());
+            }
+            match first {
+                'A'..='Z' => term(Kind::TypeName, 1 + scan(&line[1..], is_word)),
+                '{' => term(Kind::BlockStart, 1),
+                '}' => term(Kind::BlockEnd, 1),
+                '"' => term(Kind::StringDelimiter, 1),
+                ',' => term(Kind::Separator, 1),
+                '_' => term(Kind::VoidSymbol, 1),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Shortens a number following a member accessor to its leading digits, so that `t.1.0` accesses the element 0 of the element 1 of `t`
+pub fn tuple_index(term: Term<'_>) -> Term<'_> {
+    let length = scan(term.text, |c| c.is_ascii_digit());
+    match term.kind {
+        Kind::Number if length > 0 => Term {
+            kind: Kind::Number,
+            text: &term.text[..length],
+        },
+        _ => term,
+    }
+}
+
+/// Equivalent to regex's `\w`
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_symbol(c: char) -> bool {
+    c.is_ascii_lowercase() || c == '_' || c.is_numeric()
+}
+
+fn is_number(c: char) -> bool {
+    is_word(c) || c == '.'
+}
+
+fn scan(text: &str, accept: impl Fn(char) -> bool) -> usize {
+    //! Returns the length in bytes of the longest prefix of `text` whose chars are all accepted
+    text.find(|c| !accept(c)).unwrap_or(text.len())
+}
+
+fn scan_number(text: &str) -> usize {
+    /*! Returns the length of a number literal; the exponent of decimal literals may be signed (`1e-3`).
+    A trailing dot followed by a pattern is a method call rather than part of the number, as in `3.'pow(2)`
+    */
+    let radix = text.starts_with("0x") || text.starts_with("0b") || text.starts_with("0o");
+    let mut length = 0;
+    loop {
+        length += scan(&text[length..], is_number);
+        let exponent = !radix && text[..length].ends_with(['e', 'E']);
+        if exponent && text[length..].starts_with(['+', '-']) {
+            length += 1;
+        } else if text[..length].ends_with('.') && text[length..].starts_with('\'') {
+            return length - 1;
+        } else {
+            return length;
+        }
+    }
+}
+
+fn scan_type(text: &str) -> Option<usize> {
+    //! Returns the length of a type annotation (`<Shape>`, `<!Shape>` or `<~Shape>`) at the start of `text`, if there is one
+    let mut length = 1 + scan(text.strip_prefix('<')?, char::is_whitespace);
+    if text[length..].starts_with(['!', '~']) {
+        length += 1;
+        length += scan(&text[length..], char::is_whitespace);
+    }
+    if !text[length..].starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    length += scan(&text[length..], is_word);
+    length += scan(&text[length..], char::is_whitespace);
+    if text[length..].starts_with('>') {
+        Some(length + 1)
+    } else {
+        None
+    }
+}
+
+fn scan_pattern(text: &str) -> usize {
+    //! Returns the length of the name of a pattern, made of word characters and `::` separators
+    let mut length = 0;
+    loop {
+        let rest = &text[length..];
+        if rest.starts_with(is_word) {
+            length += scan(rest, is_word);
+        } else if rest.starts_with("::") {
+            length += 2;
+        } else {
+            return length;
+        }
+    }
+}
@@ -1,11 +1,16 @@
-use regex::Regex;
 use std::process;
 
+pub mod comment;
 pub mod construct;
+pub mod lexer;
+pub mod number;
+pub mod string;
 pub mod token;
 use super::ast::{ASTKind, AST};
 use super::error::{CompError, CompLocation};
 use crate::{Location, SrcFile};
+use comment::skip_block_comment;
+use string::read_string;
 use token::{Token, TokenTree};
 
 /** Parses a given file and outputs its token tree's root node.
@@ -30,34 +35,20 @@ pub fn parse<'a>(file: &'a SrcFile) -> TokenTree<'a> {
         Location::start(file),
     ));
 
-    // generate the Regex objects from the MATCHERS array
-    let mut regexes: Vec<(token::Kind, Regex)> = Vec::new();
-    for matcher in MATCHERS.iter() {
-        regexes.push((
-            matcher.0,
-            match Regex::new(matcher.1) {
-                Ok(val) => val,
-                _ => {
-                    eprintln!("     __\n  _ / /\n (_) | \n   | | \n  _| | \n (_) | \n    \\_\\\n"); // x3
-                    process::exit(1);
-                }
-            },
-        ));
-    }
-
-    for (index, line) in lines.iter().enumerate() {
-        // for each line
+    let mut index = 0usize;
+    while index < lines.len() {
+        // for each line; terms spanning several lines (like multi-line strings) move `index` forward
         //      v-- mut &str
-        let mut trimmed_line = line.clone(); // a copy of the line, which progressively gets trimmed
+        let mut trimmed_line = lines[index]; // a copy of the line, which progressively gets trimmed
         let mut current_char = 0usize;
         while trimmed_line.len() > 0 {
             let matched = match_next_term(
                 file,
-                index,
+                &lines,
+                &mut index,
                 &mut current_char,
                 &mut trimmed_line,
                 &mut token_stack,
-                &regexes,
             );
             if !matched {
                 CompError::new(
@@ -68,6 +59,7 @@ pub fn parse<'a>(file: &'a SrcFile) -> TokenTree<'a> {
                 .print_and_exit();
             }
         }
+        index += 1;
     }
 
     if token_stack.len() > 1 {
@@ -111,208 +103,141 @@ pub fn construct<'a>(parsed: TokenTree<'a>) -> AST<'a> {
 
 /** Attempts to match a TokenTree term. On success, it appends it to `token_stack`
     @param file - The current source file
-    @param line_index - The current line of code
+    @param lines - The lines of the source file
+    @param line_index - The current line of code; terms spanning several lines move it to their last line
     @param char_index - The current char within that line of code
     @param trimmed_line - The trimmed line; it is gradually trimmed after each successful match
     @param token_stack - The token stack: contains the openned blocks, tuples, etc.
-    @param regexes - The set of regexes to match with
+    @returns wether or not a term was recognized
 
-    The matching process is done by trying to match the source to each term matcher (parsed down into `regexes`).
-    Strings and nesting is also handled by this function. Nesting uses `token_stack`, which grows as we get more and more nested and decreases whenever a block or tuple ends.
+    The term itself is recognized by `lexer::next_term`.
+    Strings, block comments and nesting is also handled by this function. Nesting uses `token_stack`, which grows as we get more and more nested and decreases whenever a block or tuple ends.
 
     <!-- (Thanks to @PhirosWolf for having helped me with this) -->
 **/
 fn match_next_term<'a>(
     file: &'a SrcFile,
-    line_index: usize,
+    lines: &[&'a str],
+    line_index: &mut usize,
     char_index: &mut usize,
-    trimmed_line: &mut &str,
+    trimmed_line: &mut &'a str,
     token_stack: &mut Vec<TokenTree<'a>>,
-    regexes: &Vec<(token::Kind, Regex)>,
 ) -> bool {
     let raw: &str = &file.contents;
-    let mut res = false; // wether or not a match occured
-    for matcher in regexes.iter() {
-        if let Some(caps) = matcher.1.captures(trimmed_line) {
-            let cap_length = caps.get(0).unwrap().as_str().len();
-            let old_char_index = *char_index;
-            *char_index += cap_length;
-            *trimmed_line = trimmed_line.split_at(cap_length).1;
-            match matcher.0 {
-                token::Kind::Space => { /* noop */ }
-                token::Kind::Comment => {
-                    *trimmed_line = "";
-                    res = true;
-                    break;
-                }
-                token::Kind::TupleStart => {
-                    token_stack.push(TokenTree::new(
-                        token::Kind::Tuple,
-                        Location::new(file, line_index, old_char_index),
-                    ));
-                }
-                token::Kind::BlockStart => {
-                    token_stack.push(TokenTree::new(
-                        token::Kind::Block,
-                        Location::new(file, line_index, old_char_index),
-                    ));
-                }
-                token::Kind::TupleEnd => {
-                    if let Some(ast) = token_stack.pop() {
-                        match ast.kind {
-                            token::Kind::Tuple => {}
-                            _ => {
-                                CompError::new(
-                                    101,
-                                    String::from("Unexpected token TupleEnd ')': not in a tuple"),
-                                    CompLocation::Char(raw, line_index, *char_index - 1),
-                                )
-                                .print_and_exit();
-                            }
-                        }
-                        if let Some(parent_ast) = token_stack.last_mut() {
-                            parent_ast.tokens.push((
-                                token::Token::Tuple(ast),
-                                Location::new(file, line_index, old_char_index),
-                            ));
-                        } else {
-                            eprintln!("Empty token stack (2)");
-                            process::exit(1);
-                        }
-                    } else {
-                        eprintln!("Empty token stack (3)");
-                        process::exit(1);
+    let term = match lexer::next_term(trimmed_line) {
+        Some(term) => term,
+        None => return false,
+    };
+    let follows_accessor = matches!(
+        token_stack.last().and_then(|tree| tree.tokens.last()),
+        Some((token::Token::Operator(token::Operator::MemberAccessor), _))
+    );
+    let term = if follows_accessor { lexer::tuple_index(term) } else { term };
+    let length = term.text.len();
+    let old_char_index = *char_index;
+    *char_index += length;
+    *trimmed_line = trimmed_line.split_at(length).1;
+    let start_line = *line_index;
+    match term.kind {
+        token::Kind::Space => { /* noop */ }
+        token::Kind::Comment => *trimmed_line = "",
+        token::Kind::BlockComment => {
+            skip_block_comment(file, lines, line_index, char_index, trimmed_line)
+        }
+        token::Kind::DocComment if !lines[start_line][..old_char_index].trim().is_empty() => {
+            // doc comments following code on the same line are plain comments
+            *trimmed_line = "";
+        }
+        token::Kind::TupleStart => {
+            token_stack.push(TokenTree::new(
+                token::Kind::Tuple,
+                Location::new(file, start_line, old_char_index),
+            ));
+        }
+        token::Kind::BlockStart => {
+            token_stack.push(TokenTree::new(
+                token::Kind::Block,
+                Location::new(file, start_line, old_char_index),
+            ));
+        }
+        token::Kind::TupleEnd => {
+            if let Some(ast) = token_stack.pop() {
+                match ast.kind {
+                    token::Kind::Tuple => {}
+                    _ => {
+                        CompError::new(
+                            101,
+                            String::from("Unexpected token TupleEnd ')': not in a tuple"),
+                            CompLocation::Char(raw, start_line, *char_index - 1),
+                        )
+                        .print_and_exit();
                     }
                 }
-                token::Kind::BlockEnd => {
-                    if let Some(ast) = token_stack.pop() {
-                        match ast.kind {
-                            token::Kind::Block => {}
-                            _ => {
-                                CompError::new(
-                                    102,
-                                    String::from("Unexpected token BlockEnd '}': not in a block"),
-                                    CompLocation::Char(raw, line_index, *char_index - 1),
-                                )
-                                .print_and_exit();
-                            }
-                        }
-                        if let Some(parent_ast) = token_stack.last_mut() {
-                            parent_ast.tokens.push((
-                                token::Token::Block(ast),
-                                Location::new(file, line_index, old_char_index),
-                            ));
-                        } else {
-                            eprintln!("Empty token stack (4)");
-                            process::exit(1);
-                        }
-                    } else {
-                        eprintln!("Empty token stack (5)");
-                        process::exit(1);
-                    }
+                if let Some(parent_ast) = token_stack.last_mut() {
+                    parent_ast.tokens.push((
+                        token::Token::Tuple(ast),
+                        Location::new(file, start_line, old_char_index),
+                    ));
+                } else {
+                    eprintln!("Empty token stack (2)");
+                    process::exit(1);
                 }
-                token::Kind::StringDelimiter => {
-                    let mut iter = trimmed_line.chars();
-                    let mut was_backslash = false;
-                    let mut length = 0usize;
-                    let mut buff = String::new();
-                    loop {
-                        match iter.next() {
-                            Some(current_char) => {
-                                length += 1;
-                                if was_backslash {
-                                    was_backslash = false;
-                                    match current_char {
-                                        '\\' => buff.push('\\'),
-                                        '"' => buff.push('"'),
-                                        'n' => buff.push('\n'),
-                                        _ => {
-                                            CompError::new(
-                                                103,
-                                                format!("Unexpected character following backslash in string literal: {}", current_char),
-                                                CompLocation::Char(raw, line_index, *char_index + length - 1)
-                                            ).print_and_exit();
-                                        }
-                                    }
-                                } else {
-                                    match current_char {
-                                        '\\' => was_backslash = true,
-                                        '"' => break,
-                                        _ => buff.push(current_char),
-                                    }
-                                }
-                            }
-                            None => {
-                                CompError::new(
-                                    103,
-                                    String::from("Unexpected EOL in string literal"),
-                                    CompLocation::Char(raw, line_index, *char_index + length - 1),
-                                )
-                                .print_and_exit();
-                            }
-                        }
-                    }
-                    *char_index += length;
-                    *trimmed_line = trimmed_line.split_at(length).1;
-                    if let Some(t) = token_stack.last_mut() {
-                        t.tokens.push((
-                            token::Token::String(buff),
-                            Location::new(file, line_index, old_char_index),
-                        ));
-                    } else {
-                        eprintln!("Empty token stack (6)");
-                        process::exit(1);
+            } else {
+                eprintln!("Empty token stack (3)");
+                process::exit(1);
+            }
+        }
+        token::Kind::BlockEnd => {
+            if let Some(ast) = token_stack.pop() {
+                match ast.kind {
+                    token::Kind::Block => {}
+                    _ => {
+                        CompError::new(
+                            102,
+                            String::from("Unexpected token BlockEnd '}': not in a block"),
+                            CompLocation::Char(raw, start_line, *char_index - 1),
+                        )
+                        .print_and_exit();
                     }
                 }
-                _ => {
-                    let term = token::Token::from_match(
-                        &caps,
-                        &matcher.0,
-                        Location::new(file, line_index, old_char_index),
-                    );
-                    if let Some(t) = token_stack.last_mut() {
-                        t.tokens
-                            .push((term, Location::new(file, line_index, old_char_index)));
-                    } else {
-                        eprintln!("Empty token stack (7)");
-                        process::exit(1);
-                    }
+                if let Some(parent_ast) = token_stack.last_mut() {
+                    parent_ast.tokens.push((
+                        token::Token::Block(ast),
+                        Location::new(file, start_line, old_char_index),
+                    ));
+                } else {
+                    eprintln!("Empty token stack (4)");
+                    process::exit(1);
                 }
-            };
-            res = true;
-            break;
+            } else {
+                eprintln!("Empty token stack (5)");
+                process::exit(1);
+            }
         }
-    }
-    res
+        token::Kind::StringDelimiter => {
+            let string = read_string(file, lines, line_index, char_index, trimmed_line);
+            if let Some(t) = token_stack.last_mut() {
+                t.tokens
+                    .push((string, Location::new(file, start_line, old_char_index)));
+            } else {
+                eprintln!("Empty token stack (6)");
+                process::exit(1);
+            }
+        }
+        _ => {
+            let term = token::Token::from_term(
+                term.text,
+                &term.kind,
+                Location::new(file, start_line, old_char_index),
+            );
+            if let Some(t) = token_stack.last_mut() {
+                t.tokens
+                    .push((term, Location::new(file, start_line, old_char_index)));
+            } else {
+                eprintln!("Empty token stack (7)");
+                process::exit(1);
+            }
+        }
+    };
+    true
 }
-
-pub const MATCHERS: [(token::Kind, &str); 22] = [
-    (token::Kind::Boolean, "^(true|false)"),
-    (token::Kind::Let, "^let"),
-    (token::Kind::Struct, "^struct"),
-    (token::Kind::Use, "^#use"),
-    (token::Kind::Load, "^#load"),
-    (token::Kind::Symbol, "^_?[a-z][a-z_\\d]*"),
-    (token::Kind::Define, "^:"),
-    (token::Kind::Space, "^[\\s\\t]+"),
-    (token::Kind::Comment, "^//"),
-    (token::Kind::Pattern, "^['#]\\w(?:[\\w_\\d]|::)*"),
-    (token::Kind::TupleStart, "^\\("),
-    (token::Kind::TupleEnd, "^\\)"),
-    (token::Kind::Number, "^-?\\d+(?:\\.\\d*)?[\\w.]*"), // intentionally loose
-    (token::Kind::Arrow, "^=>"),
-    (
-        token::Kind::Operator,
-        "^(->|\\.|>=|<=|==|!=|&&|\\|\\||[!+\\-/*<>%]|~)",
-    ),
-    (
-        token::Kind::Type,
-        "^<\\s*([!~]?)\\s*([A-Z][\\w_\\d]*|number|bool|string|function)\\s*>",
-    ),
-    (token::Kind::TypeName, "^[A-Z][\\w_\\d]*"),
-    (token::Kind::BlockStart, "^\\{"),
-    (token::Kind::BlockEnd, "^\\}"),
-    (token::Kind::StringDelimiter, "^\""),
-    (token::Kind::Separator, "^,"),
-    (token::Kind::VoidSymbol, "^_"),
-];
@@ -0,0 +1,119 @@
+//! Reads number literals: decimal numbers with an optional fraction and exponent (`1.5e-3`), as well as hexadecimal (`0xFF`), binary (`0b1010`) and octal (`0o17`) integers.
+//! Digits may be separated by underscores (`1_000_000`).
+
+use super::{CompError, CompLocation};
+use crate::Location;
+
+/** Returns the value of the number literal `text`, as recognized by `lexer::next_term`.
+    @param loc - The location of the literal; errors point at the offending char within it
+**/
+pub fn read_number(text: &str, loc: &Location) -> f64 {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let offset = text.len() - digits.len();
+
+    let radix = match digits.get(..2) {
+        Some("0x") => Some((16, "hexadecimal")),
+        Some("0b") => Some((2, "binary")),
+        Some("0o") => Some((8, "octal")),
+        _ => None,
+    };
+    let value = match radix {
+        Some((radix, name)) => read_integer(&digits[2..], radix, name, offset + 2, loc),
+        None => read_decimal(digits, offset, loc),
+    };
+
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+fn read_integer(digits: &str, radix: u32, name: &str, offset: usize, loc: &Location) -> f64 {
+    //! Reads the digits of a hexadecimal, binary or octal literal, following its prefix
+    if digits.is_empty() {
+        error(format!("Expected digits following the {} prefix", name), offset, loc);
+    }
+    let mut value = 0f64;
+    for (pos, c) in digits.char_indices() {
+        match c.to_digit(radix) {
+            Some(digit) => value = value * radix as f64 + digit as f64,
+            None if c == '_' => check_separator(digits, pos, radix, offset, loc),
+            None => error(
+                format!("Invalid digit '{}' in {} number literal", c, name),
+                offset + pos,
+                loc,
+            ),
+        }
+    }
+    value
+}
+
+fn read_decimal(digits: &str, offset: usize, loc: &Location) -> f64 {
+    //! Reads a decimal literal, made of an integer part, an optional fraction (`.5`) and an optional exponent (`e-3`)
+    let mut cleaned = String::with_capacity(digits.len());
+    let mut seen_dot = false;
+    let mut exponent: Option<usize> = None; // position of the `e`
+
+    for (pos, c) in digits.char_indices() {
+        match c {
+            '0'..='9' => cleaned.push(c),
+            '_' => check_separator(digits, pos, 10, offset, loc),
+            '.' if !seen_dot && exponent.is_none() => {
+                seen_dot = true;
+                cleaned.push(c);
+            }
+            'e' | 'E' if exponent.is_none() => {
+                exponent = Some(pos);
+                cleaned.push('e');
+            }
+            '+' | '-' if exponent.map(|e| e + 1) == Some(pos) => cleaned.push(c),
+            _ => error(
+                format!("Unexpected character '{}' in number literal", c),
+                offset + pos,
+                loc,
+            ),
+        }
+    }
+
+    if let Some(pos) = exponent {
+        let exp = digits[pos + 1..].trim_start_matches(['+', '-']);
+        if !exp.starts_with(|c: char| c.is_ascii_digit()) {
+            error(
+                String::from("Expected digits in the exponent of number literal"),
+                offset + digits.len() - exp.len(),
+                loc,
+            );
+        }
+    }
+
+    match cleaned.parse::<f64>() {
+        Ok(value) => value,
+        Err(e) => error(
+            format!("Invalid number literal: {} ({})", digits, e),
+            offset,
+            loc,
+        ),
+    }
+}
+
+fn check_separator(digits: &str, pos: usize, radix: u32, offset: usize, loc: &Location) {
+    //! Digit separators must be surrounded by digits: `1_000` is valid, while `1__000`, `1_` and `1_.5` aren't
+    let is_digit = |c: char| c.is_digit(radix);
+    let before = digits[..pos].chars().next_back().is_some_and(is_digit);
+    let after = digits[pos + 1..].chars().next().is_some_and(is_digit);
+    if !before || !after {
+        error(
+            String::from("Digit separators ('_') must be placed between two digits"),
+            offset + pos,
+            loc,
+        );
+    }
+}
+
+fn error(msg: String, pos: usize, loc: &Location) -> ! {
+    CompError::new(6, msg, CompLocation::Char(loc.src, loc.line, loc.ch + pos)).print_and_exit()
+}
@@ -0,0 +1,254 @@
+//! Reads string literals, which may span several lines (`"""`), contain escape sequences and embed expressions (`"x = {x}"`).
+
+use super::token::{Kind, StringPart, Token, TokenTree};
+use super::{match_next_term, CompError, CompLocation};
+use crate::{Location, SrcFile};
+
+/** Reads the rest of a string literal, whose opening `"` has just been consumed, and returns its token.
+    @param file - The current source file
+    @param lines - The lines of the source file
+    @param line_index - The current line of code; it is moved to the line where the string ends
+    @param char_index - The current char within that line of code
+    @param trimmed_line - The trimmed line, starting right after the opening `"`
+
+    Triple-quoted strings (`"""`) may span several lines, which are joined with `\n`.
+    Expressions within braces are tokenized like the rest of the source code; strings containing such expressions yield a `Token::InterpolatedString`.
+**/
+pub fn read_string<'a>(
+    file: &'a SrcFile,
+    lines: &[&'a str],
+    line_index: &mut usize,
+    char_index: &mut usize,
+    trimmed_line: &mut &'a str,
+) -> Token<'a> {
+    let raw: &str = &file.contents;
+    let start = (*line_index, *char_index - 1);
+    let triple = trimmed_line.starts_with("\"\"");
+    if triple {
+        *char_index += 2;
+        *trimmed_line = &trimmed_line[2..];
+    }
+
+    let mut parts: Vec<StringPart<'a>> = Vec::new();
+    let mut buff = String::new();
+    loop {
+        let line: &'a str = trimmed_line;
+        let mut pos = 0usize; // position of the current char within `line`
+        let mut end: Option<usize> = None; // position following the closing delimiter
+
+        while let Some(current_char) = line[pos..].chars().next() {
+            let next = pos + current_char.len_utf8();
+            match current_char {
+                '\\' => {
+                    pos = read_escape(raw, line, next, (*line_index, *char_index), &mut buff);
+                    continue;
+                }
+                '"' if !triple => {
+                    end = Some(next);
+                    break;
+                }
+                '"' if line[pos..].starts_with("\"\"\"") => {
+                    end = Some(pos + 3);
+                    break;
+                }
+                '{' => {
+                    let length = interpolation_length(&line[next..]).unwrap_or_else(|| {
+                        CompError::new(
+                            103,
+                            String::from("Unterminated interpolation in string literal: expected a '}'"),
+                            CompLocation::Char(raw, *line_index, *char_index + pos),
+                        )
+                        .print_and_exit();
+                    });
+                    let tree = read_interpolation(
+                        file,
+                        &lines[..=*line_index],
+                        *line_index,
+                        *char_index + next,
+                        &line[next..next + length],
+                    );
+                    parts.push(StringPart::Literal(std::mem::take(&mut buff)));
+                    parts.push(StringPart::Expression(tree));
+                    pos = next + length + 1;
+                    continue;
+                }
+                _ => buff.push(current_char),
+            }
+            pos = next;
+        }
+
+        match end {
+            Some(length) => {
+                *char_index += length;
+                *trimmed_line = &line[length..];
+                break;
+            }
+            None if triple => {
+                if *line_index + 1 >= lines.len() {
+                    CompError::new(
+                        103,
+                        String::from("Unexpected EOF in multi-line string literal"),
+                        CompLocation::Char(raw, start.0, start.1),
+                    )
+                    .print_and_exit();
+                }
+                buff.push('\n');
+                *line_index += 1;
+                *char_index = 0;
+                *trimmed_line = lines[*line_index];
+            }
+            None => {
+                CompError::new(
+                    103,
+                    String::from("Unexpected EOL in string literal"),
+                    CompLocation::Char(raw, *line_index, (*char_index + line.len()).saturating_sub(1)),
+                )
+                .print_and_exit();
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        Token::String(buff)
+    } else {
+        parts.push(StringPart::Literal(buff));
+        Token::InterpolatedString(parts)
+    }
+}
+
+/** Reads an escape sequence, whose backslash precedes `line[pos..]`, into `buff`, returning the position following it.
+    @param at - The line and char at which `line` starts, for error reporting
+**/
+fn read_escape(raw: &str, line: &str, pos: usize, at: (usize, usize), buff: &mut String) -> usize {
+    let escaped = match line[pos..].chars().next() {
+        Some(c) => c,
+        None => CompError::new(
+            103,
+            String::from("Unexpected EOL in string literal"),
+            CompLocation::Char(raw, at.0, at.1 + pos - 1),
+        )
+        .print_and_exit(),
+    };
+    let next = pos + escaped.len_utf8();
+
+    let (c, next) = match escaped {
+        '\\' => ('\\', next),
+        '"' => ('"', next),
+        'n' => ('\n', next),
+        't' => ('\t', next),
+        'r' => ('\r', next),
+        '0' => ('\0', next),
+        '{' => ('{', next),
+        'u' => {
+            // \u{XXXX}, with one to six hexadecimal digits
+            if !line[next..].starts_with('{') {
+                CompError::new(
+                    103,
+                    String::from("Expected '{' following \\u in string literal"),
+                    CompLocation::Char(raw, at.0, at.1 + next),
+                )
+                .print_and_exit();
+            }
+            let digits = &line[next + 1..];
+            let length = digits.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(digits.len());
+            if length == 0 || length > 6 || !digits[length..].starts_with('}') {
+                CompError::new(
+                    103,
+                    String::from("Invalid unicode escape in string literal: expected one to six hexadecimal digits followed by '}'"),
+                    CompLocation::Char(raw, at.0, at.1 + next + 1 + length.min(6)),
+                )
+                .print_and_exit();
+            }
+            let code = u32::from_str_radix(&digits[..length], 16).unwrap();
+            let c = std::char::from_u32(code).unwrap_or_else(|| {
+                CompError::new(
+                    103,
+                    format!("Invalid unicode escape in string literal: {:X} is not a valid char", code),
+                    CompLocation::Char(raw, at.0, at.1 + next + 1),
+                )
+                .print_and_exit();
+            });
+            (c, next + length + 2)
+        }
+        _ => CompError::new(
+            103,
+            format!("Unexpected character following backslash in string literal: {}", escaped),
+            CompLocation::Char(raw, at.0, at.1 + pos),
+        )
+        .print_and_exit(),
+    };
+
+    buff.push(c);
+    next
+}
+
+fn interpolation_length(text: &str) -> Option<usize> {
+    //! Returns the length of the expression embedded at the start of `text`, up to its closing brace; braces within the string literals of the expression are skipped
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut chars = text.char_indices();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(pos),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/** Tokenizes an expression embedded in a string literal.
+    @param lines - The lines of the source file, up to the current one: embedded expressions cannot span several lines
+    @param char_index - The char at which `text` starts within the current line
+**/
+fn read_interpolation<'a>(
+    file: &'a SrcFile,
+    lines: &[&'a str],
+    line_index: usize,
+    char_index: usize,
+    text: &'a str,
+) -> TokenTree<'a> {
+    let raw: &str = &file.contents;
+    let mut token_stack = vec![TokenTree::new(
+        Kind::TokenTreeRoot,
+        Location::new(file, line_index, char_index - 1),
+    )];
+    let mut current_line = line_index;
+    let mut current_char = char_index;
+    let mut trimmed = text;
+
+    while !trimmed.is_empty() {
+        let matched = match_next_term(
+            file,
+            lines,
+            &mut current_line,
+            &mut current_char,
+            &mut trimmed,
+            &mut token_stack,
+        );
+        if !matched {
+            CompError::new(
+                3,
+                String::from("Unrecognized term"),
+                CompLocation::Char(raw, line_index, current_char),
+            )
+            .print_and_exit();
+        }
+    }
+
+    if token_stack.len() > 1 {
+        CompError::new(
+            5,
+            String::from("Unexpected end of interpolation; did you forget a closing parenthesis?"),
+            CompLocation::Char(raw, line_index, current_char),
+        )
+        .print_and_exit();
+    }
+    token_stack.pop().unwrap()
+}
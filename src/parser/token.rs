@@ -1,6 +1,5 @@
-use crate::error::*;
+use super::number::read_number;
 use crate::Location;
-use regex::Captures;
 use std::fmt;
 
 // tokens that will end up in the TokenTree
@@ -10,8 +9,16 @@ pub enum Token<'a> {
     Symbol(String),
     VoidSymbol,
     Define,
+    /// `+:`, `-:`, ... which define a variable or a member to the result of the operator on its previous value and the given value
+    CompoundDefine(Operator),
     Let,
+    Const,
+    Static,
     Struct,
+    Extends,
+    Interface,
+    Implements,
+    Enum,
     Use,
     Load,
     Pattern(String),
@@ -23,46 +30,41 @@ pub enum Token<'a> {
     Type(Type),
     TypeName(TypeName),
     String(String),
+    InterpolatedString(Vec<StringPart<'a>>),
     Separator,
+    DocComment(String),
+}
+
+/// A part of an interpolated string literal, ie. `"x = {x}"`
+#[derive(Debug, Clone)]
+pub enum StringPart<'a> {
+    Literal(String),
+    /// The tokens of an embedded expression, within a `TokenTreeRoot`
+    Expression(TokenTree<'a>),
 }
 
 impl<'a> Token<'a> {
-    pub fn from_match(caps: &Captures, matcher: &Kind, loc: Location) -> Token<'a> {
-        match matcher {
-            Kind::Boolean => Token::Boolean(caps.get(1).unwrap().as_str() == "true"),
+    pub fn from_term(text: &str, kind: &Kind, loc: Location) -> Token<'a> {
+        //! Creates a token out of a term recognized by `lexer::next_term`
+        match kind {
+            Kind::Boolean => Token::Boolean(text == "true"),
             Kind::Let => Token::Let,
-            Kind::Symbol => Token::Symbol(String::from(caps.get(0).unwrap().as_str())),
+            Kind::Const => Token::Const,
+            Kind::Static => Token::Static,
+            Kind::Symbol => Token::Symbol(String::from(text)),
             Kind::Define => Token::Define,
-            Kind::Pattern => Token::Pattern(String::from(caps.get(0).unwrap().as_str())),
-            Kind::Number => Token::Number(match caps.get(0).unwrap().as_str().parse::<f64>() {
-                Ok(v) => v,
-                Err(e) => {
-                    CompError::new(
-                        6,
-                        format!(
-                            "Invalid number literal: {} ({})",
-                            caps.get(0).unwrap().as_str(),
-                            e
-                        ),
-                        CompLocation::from(loc),
-                    )
-                    .print_and_exit();
-                }
-            }),
+            Kind::CompoundDefine => match Token::from_term(&text[..1], &Kind::Operator, loc) {
+                Token::Operator(op) => Token::CompoundDefine(op),
+                _ => unreachable!(),
+            },
+            Kind::Pattern => Token::Pattern(String::from(text)),
+            Kind::Number => Token::Number(read_number(text, &loc)),
             Kind::Arrow => Token::Arrow,
             Kind::VoidSymbol => Token::VoidSymbol,
             Kind::TypeName => Token::TypeName(TypeName {
-                name: String::from(caps.get(0).unwrap().as_str()),
+                name: String::from(text),
             }),
-            Kind::Type => Token::Type(Type {
-                name: String::from(caps.get(2).unwrap().as_str()),
-                strictness: match caps.get(1).unwrap().as_str() {
-                    "!" => TypeStrictness::Strict,
-                    "~" => TypeStrictness::Loose,
-                    _ => TypeStrictness::Normal,
-                },
-            }),
-            Kind::Operator => Token::Operator(match caps.get(1).unwrap().as_str() {
+            Kind::Operator => Token::Operator(match text {
                 "->" => Operator::Interpretation,
                 "==" => Operator::Eq,
                 "!=" => Operator::Neq,
@@ -80,17 +82,39 @@ impl<'a> Token<'a> {
                 "%" => Operator::Mod,
                 "." => Operator::MemberAccessor,
                 "~" => Operator::PartialApplication,
+                "|>" => Operator::Pipe,
+                ">>" => Operator::Compose,
                 _ => {
-                    eprintln!("Unknown operator: {:?}", caps.get(1).unwrap().as_str());
+                    eprintln!("Unknown operator: {:?}", text);
                     std::process::exit(1);
                 }
             }),
             Kind::Struct => Token::Struct,
+            Kind::Extends => Token::Extends,
+            Kind::Interface => Token::Interface,
+            Kind::Implements => Token::Implements,
+            Kind::Enum => Token::Enum,
+            Kind::Type => {
+                let inner = text[1..text.len() - 1].trim();
+                let (strictness, name) = match inner.chars().next() {
+                    Some('!') => (TypeStrictness::Strict, inner[1..].trim_start()),
+                    Some('~') => (TypeStrictness::Loose, inner[1..].trim_start()),
+                    _ => (TypeStrictness::Normal, inner),
+                };
+                Token::Type(Type {
+                    name: String::from(name),
+                    strictness,
+                })
+            }
             Kind::Load => Token::Load,
             Kind::Use => Token::Use,
             Kind::Separator => Token::Separator,
+            Kind::DocComment => {
+                let text = &text[3..];
+                Token::DocComment(String::from(text.strip_prefix(' ').unwrap_or(text)))
+            }
             _ => {
-                eprintln!("Unknown token kind: {:?}", matcher);
+                eprintln!("Unknown token kind: {:?}", kind);
                 std::process::exit(4);
             }
         }
@@ -121,12 +145,21 @@ pub enum Kind {
     Symbol,
     VoidSymbol,
     Define,
+    CompoundDefine,
     Space,
     Let,
+    Const,
+    Static,
     Struct,
+    Extends,
+    Interface,
+    Implements,
+    Enum,
     Use,
     Load,
     Comment,
+    BlockComment,
+    DocComment,
     Pattern,
     TupleStart,
     TupleEnd,
@@ -150,7 +183,7 @@ pub struct Type {
     pub strictness: TypeStrictness,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TypeStrictness {
     Loose,
     Normal,
@@ -179,6 +212,10 @@ pub enum Operator {
     Interpretation,
     MemberAccessor,
     PartialApplication,
+    /// `x |> f`, which calls `f` with `x` as its first argument
+    Pipe,
+    /// `f >> g`, which builds the function calling `g` with the result of `f`
+    Compose,
     Gt,
     Gte,
     Lt,
@@ -210,6 +247,8 @@ impl fmt::Display for Operator {
             Operator::Interpretation => "->",
             Operator::MemberAccessor => ".",
             Operator::PartialApplication => "~",
+            Operator::Pipe => "|>",
+            Operator::Compose => ">>",
             Operator::Gt => ">",
             Operator::Gte => ">=",
             Operator::Lt => "<",
@@ -1,5 +1,5 @@
 // This only contains test utilities
-use super::{ast, internal, error, interpreter, parser, SrcFile};
+use super::{ast, internal, error, interpreter, parser, vm, SrcFile};
 use std::fs;
 
 pub fn init_testenv() {
@@ -7,6 +7,10 @@ pub fn init_testenv() {
     internal::TEST_LOG.with(|t| *t.borrow_mut() = String::new());
 }
 
+pub fn set_max_call_depth(depth: usize) {
+    interpreter::MAX_CALL_DEPTH.with(|d| *d.borrow_mut() = depth);
+}
+
 pub fn load(path: &str) -> SrcFile {
     let path = String::from(path);
     let raw = match fs::read_to_string(&path) {
@@ -32,6 +36,10 @@ pub fn execute<'a>(program: ast::RASTRef<'a>) -> interpreter::VariableValue<'a>
     interpreter::interprete(program, Vec::new())
 }
 
+pub fn execute_vm<'a>(program: ast::RASTRef<'a>) -> interpreter::VariableValue<'a> {
+    vm::run(program)
+}
+
 pub fn get_logs() -> String {
     internal::TEST_LOG.with(|t| t.borrow().clone())
 }
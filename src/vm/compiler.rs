@@ -0,0 +1,374 @@
+use super::*;
+
+/// Where a variable is stored, relative to the scopes of a chunk
+enum VarSlot {
+    Local { up: usize, slot: usize },
+    Outer { depth: usize, ulid: u128, slot: usize },
+}
+
+/** Compiles `RAST`s into a `Chunk`.
+    Each construct is compiled to instructions which have the same effect as what `interpreter::interprete_instruction` and its siblings do with it, scopes and `last_value`s included.
+**/
+pub struct Compiler<'a> {
+    chunk: Chunk<'a>,
+    /// The scopes that will have been entered at this point of the chunk, innermost last
+    scopes: Vec<RASTRef<'a>>,
+}
+
+impl<'a> Compiler<'a> {
+    /** Creates a new compiler.
+        @param scopes - The scopes that are already entered when the chunk starts, innermost last
+    **/
+    pub fn new(scopes: Vec<RASTRef<'a>>) -> Compiler<'a> {
+        Compiler {
+            chunk: Chunk::default(),
+            scopes,
+        }
+    }
+
+    pub fn finish(self) -> Chunk<'a> {
+        self.chunk
+    }
+
+    pub fn emit(&mut self, op: Op<'a>, location: Location<'a>) {
+        self.chunk.ops.push(op);
+        self.chunk.locations.push(location);
+    }
+
+    pub fn scope(&mut self, ast: RASTRef<'a>, location: Location<'a>) {
+        //! Compiles a block, which leaves its value on the stack; see `interpreter::interprete`
+        self.enter(&ast, location.clone());
+        let is_tuple = matches!(ast.borrow().kind, ASTKind::Tuple | ASTKind::ArgTuple);
+        for (instruction, location) in &ast.borrow().instructions {
+            self.instruction(instruction, location.clone());
+            if is_tuple {
+                self.emit(Op::KeepLast, location.clone());
+            } else {
+                self.emit(Op::SetLast, location.clone());
+            }
+        }
+        if is_tuple {
+            self.emit(Op::Tuple(ast.borrow().instructions.len()), location.clone());
+        } else {
+            self.emit(Op::PushLast, location.clone());
+        }
+        self.exit(location);
+    }
+
+    pub fn body(&mut self, ast: RASTRef<'a>, location: Location<'a>) {
+        //! Compiles the body of a function, whose last instruction may be a tail call; see `interpreter::interprete_tail`
+        self.enter(&ast, location.clone());
+        let ast = ast.borrow();
+        match ast.instructions.split_last() {
+            Some(((last, last_location), instructions)) => {
+                for (instruction, location) in instructions {
+                    self.instruction(instruction, location.clone());
+                    self.emit(Op::SetLast, location.clone());
+                }
+                if let RASTNode::PatternCall(pat, args) = last {
+                    let layout = self.args(args.clone(), last_location.clone());
+                    self.emit(Op::TailCall(pat.clone(), layout), last_location.clone());
+                } else {
+                    self.instruction(last, last_location.clone());
+                    self.emit(Op::Return, last_location.clone());
+                }
+            }
+            None => {
+                self.emit(Op::Push(VariableValue::Nil), location.clone());
+                self.emit(Op::Return, location);
+            }
+        }
+    }
+
+    fn args(&mut self, ast: RASTRef<'a>, location: Location<'a>) -> ArgLayout {
+        //! Compiles the argument tuple of a call, leaving its arguments on the stack; see `interpreter::interprete_args`
+        self.enter(&ast, location.clone());
+        let mut layout = ArgLayout::new();
+        for (instruction, location) in &ast.borrow().instructions {
+            match instruction {
+                RASTNode::NamedArg(name, value) => {
+                    self.instruction(value, location.clone());
+                    layout.push(Some(name.clone()));
+                }
+                x => {
+                    self.instruction(x, location.clone());
+                    layout.push(None);
+                }
+            }
+            self.emit(Op::KeepLast, location.clone());
+        }
+        self.exit(location);
+        layout
+    }
+
+    fn enter(&mut self, ast: &RASTRef<'a>, location: Location<'a>) {
+        let op = {
+            let ast = ast.borrow();
+            Op::Enter {
+                depth: ast.depth,
+                ulid: ast.ulid,
+                size: ast.variables.len(),
+            }
+        };
+        self.emit(op, location);
+        self.scopes.push(ast.clone());
+    }
+
+    fn exit(&mut self, location: Location<'a>) {
+        self.emit(Op::Exit, location);
+        self.scopes.pop();
+    }
+
+    pub fn instruction(&mut self, instruction: &RASTNode<'a>, location: Location<'a>) {
+        //! Compiles a single instruction, which leaves its value on the stack; see `interpreter::interprete_instruction`
+        match instruction {
+            RASTNode::Number(x) => self.emit(Op::Push(VariableValue::Number(*x)), location),
+            RASTNode::String(x) => self.emit(Op::Push(VariableValue::String(x.clone())), location),
+            RASTNode::Interpolation(parts) => {
+                for (part, location) in parts {
+                    self.instruction(part, location.clone());
+                }
+                self.emit(Op::Interpolation(parts.len()), location);
+            }
+            RASTNode::Boolean(x) => self.emit(Op::Push(VariableValue::Boolean(*x)), location),
+            RASTNode::Tuple(elements, _is_partial) => {
+                for (element, location) in elements {
+                    self.instruction(element, location.clone());
+                }
+                self.emit(Op::Tuple(elements.len()), location);
+            }
+            RASTNode::VariableDef(var, value) => {
+                let slot = self.variable(var);
+                self.load(&slot, location.clone());
+                self.instruction(value, location.clone());
+                self.store(&slot, location);
+            }
+            RASTNode::PatternCall(pat, args) => {
+                let layout = self.args(args.clone(), location.clone());
+                self.emit(Op::Call(pat.clone(), layout), location);
+            }
+            RASTNode::Expression(expr) => self.expression(expr, location),
+            RASTNode::Block(ast) => self.scope(ast.clone(), location),
+            RASTNode::Match(value, arms) => {
+                self.scope(value.clone(), location.clone());
+                self.emit(Op::Match(arms.clone()), location);
+            }
+            RASTNode::Variable(var) => {
+                let slot = self.variable(var);
+                self.load(&slot, location);
+            }
+            RASTNode::Pattern(pat) => {
+                self.emit(Op::Push(VariableValue::Function(pat.clone(), vec![])), location)
+            }
+            RASTNode::Function(fun) => {
+                let closure: Vec<RASTRef<'a>> =
+                    fun.borrow().closure.iter().map(|(_, value)| value.clone()).collect();
+                for value in closure {
+                    self.scope(value, location.clone());
+                }
+                self.emit(Op::Function(fun.clone()), location);
+            }
+            RASTNode::TypeName(x) => self.emit(Op::Push(VariableValue::Type(x.clone())), location),
+            RASTNode::StructLiteral(st, fields) => {
+                for (_, value, location) in fields {
+                    self.instruction(value, location.clone());
+                }
+                let names = fields.iter().map(|(name, _, _)| name.clone()).collect();
+                self.emit(Op::StructLiteral(st.clone(), names), location);
+            }
+            RASTNode::ComplexDef(expr, member, value) => {
+                if let RDefineMember::Member(name) = member {
+                    self.expression(expr, location.clone());
+                    self.emit(Op::GetOld(name.clone()), location.clone());
+                    self.instruction(value, location.clone());
+                    self.emit(Op::SetMember(name.clone()), location);
+                } else {
+                    self.instruction(value, location.clone());
+                    self.emit(Op::Define(expr.clone(), member.clone()), location);
+                }
+            }
+            RASTNode::CompoundDef(target, op, value) => {
+                if let RDefineTarget::Member(expr, name) = target {
+                    self.expression(expr, location.clone());
+                    self.instruction(value, location.clone());
+                    self.emit(Op::CompoundMember(name.clone(), *op), location);
+                } else {
+                    self.instruction(value, location.clone());
+                    self.emit(Op::Compound(target.clone(), *op), location);
+                }
+            }
+            RASTNode::Nil | RASTNode::VoidSymbol => self.emit(Op::Push(VariableValue::Nil), location),
+            // members, named arguments and method calls are only ever terms of an expression or of a call's arguments, which compile them
+            RASTNode::Member(_) | RASTNode::NamedArg(_, _) | RASTNode::MethodCall(_, _, _) => {
+                self.emit(Op::Push(VariableValue::Nil), location)
+            }
+        }
+    }
+
+    fn expression(&mut self, expr: &RExpression<'a>, location: Location<'a>) {
+        //! Compiles an expression, which leaves its value on the stack; see `interpreter::interprete_expression_int`
+        self.emit(Op::BeginExpr, location.clone());
+        for term in &expr.terms {
+            match term {
+                RExprTerm::Push(RASTNode::Tuple(elements, true)) => {
+                    let mut mask = Vec::with_capacity(elements.len());
+                    for (element, location) in elements {
+                        if let RASTNode::VoidSymbol = element {
+                            mask.push(false);
+                        } else {
+                            self.instruction(element, location.clone());
+                            mask.push(true);
+                        }
+                    }
+                    self.emit(Op::PartialTuple(mask), location.clone());
+                }
+                RExprTerm::Push(RASTNode::Tuple(elements, false))
+                    if elements.iter().any(|(x, _)| matches!(x, RASTNode::NamedArg(_, _))) =>
+                {
+                    let mut layout = ArgLayout::new();
+                    for (element, location) in elements {
+                        match element {
+                            RASTNode::NamedArg(name, value) => {
+                                self.instruction(value, location.clone());
+                                layout.push(Some(name.clone()));
+                            }
+                            x => {
+                                self.instruction(x, location.clone());
+                                layout.push(None);
+                            }
+                        }
+                    }
+                    self.emit(Op::Args(layout), location.clone());
+                }
+                RExprTerm::Push(RASTNode::MethodCall(name, args, methods)) => {
+                    let layout = self.args(args.clone(), location.clone());
+                    self.emit(Op::MethodCall(name.clone(), layout, methods.clone()), location.clone());
+                }
+                RExprTerm::Push(RASTNode::Member(name)) => {
+                    self.emit(Op::Member(name.clone()), location.clone())
+                }
+                RExprTerm::Push(x) => self.instruction(x, location.clone()),
+                RExprTerm::Op(op) => self.emit(Op::Operator(*op), location.clone()),
+            }
+        }
+        self.emit(Op::EndExpr, location);
+    }
+
+    fn variable(&self, var: &RSymRef) -> VarSlot {
+        /*! Finds the scope declaring `var` by walking up from the innermost scope.
+        Variables declared within the chunk's scopes are addressed relatively to the innermost scope, while the other ones are looked up at runtime.
+        */
+        let slot = var.slot;
+        let mut current = self.scopes.last().cloned();
+        let mut up = 0;
+        while let Some(ast) = current {
+            if ast.borrow().ulid == var.ulid {
+                let is_local = up < self.scopes.len()
+                    && Rc::ptr_eq(&ast, &self.scopes[self.scopes.len() - 1 - up]);
+                return if is_local {
+                    VarSlot::Local { up, slot }
+                } else {
+                    VarSlot::Outer {
+                        depth: var.depth,
+                        ulid: var.ulid,
+                        slot,
+                    }
+                };
+            }
+            current = ast.borrow().parent.upgrade();
+            up += 1;
+        }
+        panic!("Couldn't find the scope of variable {}", var.name);
+    }
+
+    fn load(&mut self, var: &VarSlot, location: Location<'a>) {
+        match *var {
+            VarSlot::Local { up, slot } => self.emit(Op::Load { up, slot }, location),
+            VarSlot::Outer { depth, ulid, slot } => {
+                self.emit(Op::LoadOuter { depth, ulid, slot }, location)
+            }
+        }
+    }
+
+    fn store(&mut self, var: &VarSlot, location: Location<'a>) {
+        match *var {
+            VarSlot::Local { up, slot } => self.emit(Op::Store { up, slot }, location),
+            VarSlot::Outer { depth, ulid, slot } => {
+                self.emit(Op::StoreOuter { depth, ulid, slot }, location)
+            }
+        }
+    }
+}
+
+pub fn compile_function<'a>(function: &RFunction<'a>) -> FunctionChunk<'a> {
+    //! Compiles the body of `function` and the default values of its arguments, which all run within its `init` context
+    let mut compiler = Compiler::new(vec![function.body.clone()]);
+    compiler.body(function.block(), function.location.clone());
+
+    let defaults = function
+        .args
+        .iter()
+        .map(|arg| {
+            arg.default.as_ref().map(|default| {
+                let mut compiler = Compiler::new(vec![function.body.clone()]);
+                compiler.scope(default.clone(), function.location.clone());
+                compiler.emit(Op::Return, function.location.clone());
+                compiler.finish()
+            })
+        })
+        .collect();
+
+    FunctionChunk {
+        body: compiler.finish(),
+        defaults,
+    }
+}
+
+pub fn compile_match_arm<'a>(arm: &RMatchArm<'a>) -> MatchArmChunk<'a> {
+    //! Compiles the guard and the body of `arm`, which both run within the context of its scope
+    let compile = |ast: &RASTRef<'a>| {
+        let mut compiler = Compiler::new(vec![arm.scope.clone()]);
+        compiler.scope(ast.clone(), arm.location.clone());
+        compiler.emit(Op::Return, arm.location.clone());
+        compiler.finish()
+    };
+    MatchArmChunk {
+        guard: arm.guard.as_ref().map(compile),
+        body: compile(&arm.body),
+    }
+}
+
+pub fn compile_defaults<'a>(st: &RStructRef<'a>) -> Chunk<'a> {
+    //! Compiles the `let` declarations of the body of `st`, which run within the context of the body; the chunk is empty if there are none
+    //! Static members are left out, see `interpreter::instance::get_static`
+    let body = st.borrow().context.clone().expect("Expected struct to have a body");
+    let mut compiler = Compiler::new(vec![body.clone()]);
+    let mut last = None;
+    for (instruction, location) in &body.borrow().instructions {
+        if let RASTNode::VariableDef(var, _) = instruction {
+            if !var.ast_ref.borrow().is_static {
+                compiler.instruction(instruction, location.clone());
+                compiler.emit(Op::SetLast, location.clone());
+                last = Some(location.clone());
+            }
+        }
+    }
+    if let Some(location) = last {
+        compiler.emit(Op::PushLast, location.clone());
+        compiler.emit(Op::Return, location);
+    }
+    compiler.finish()
+}
+
+pub fn compile_interpretation<'a>(interpretation: &RInterpretation<'a>) -> Chunk<'a> {
+    //! Compiles the body of `interpretation`, which runs within the context declaring `from` and `to`
+    match interpretation.body.borrow().instructions.last() {
+        Some((RASTNode::Block(block), location)) => {
+            let mut compiler = Compiler::new(Vec::new());
+            compiler.scope(block.clone(), location.clone());
+            compiler.emit(Op::Return, location.clone());
+            compiler.finish()
+        }
+        _ => panic!("Expected interpretation body to end with a block"),
+    }
+}
@@ -0,0 +1,408 @@
+//! Bytecode backend: compiles `RAST`s into flat chunks of instructions and runs them on a stack machine.
+//! Scopes are pushed onto and popped off a single context stack instead of cloning it, and the contexes of the chunk's own scopes are addressed directly rather than looked up.
+//! The VM produces the same results as `interpreter::interprete`, with which it shares its values, operators and calling conventions.
+
+pub mod compiler;
+pub mod op;
+
+pub use crate::interpreter::*;
+pub use compiler::*;
+pub use op::*;
+use std::cell::{Cell, RefCell};
+use token::Operator;
+
+thread_local!(static ACTIVE: Cell<bool> = const { Cell::new(false) });
+
+pub fn is_active() -> bool {
+    //! Returns wether or not the VM is running; functions are then run by the VM too
+    ACTIVE.with(|active| active.get())
+}
+
+/// Marks the VM as running for as long as it lives
+struct Activation(bool);
+
+impl Activation {
+    fn new() -> Activation {
+        Activation(ACTIVE.with(|active| active.replace(true)))
+    }
+}
+
+impl Drop for Activation {
+    fn drop(&mut self) {
+        ACTIVE.with(|active| active.set(self.0));
+    }
+}
+
+pub fn run<'a>(program: RASTRef<'a>) -> VariableValue<'a> {
+    //! Compiles and runs a whole program, like `interpreter::interprete(program, Vec::new())` does
+    let _active = Activation::new();
+    let location = program
+        .borrow()
+        .instructions
+        .first()
+        .map(|(_, location)| location.clone());
+
+    match location {
+        Some(location) => {
+            // `program` must outlive the chunk, as its children only hold weak references to it
+            let mut compiler = Compiler::new(Vec::new());
+            compiler.scope(program.clone(), location.clone());
+            compiler.emit(Op::Return, location);
+            evaluate_chunk(&compiler.finish(), Vec::new())
+        }
+        None => VariableValue::Nil,
+    }
+}
+
+pub fn run_function<'a>(
+    function: &RFunction<'a>,
+    contexes: Vec<ContextRef<'a>>,
+    caller: Option<&Vec<ContextRef<'a>>>,
+) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+    //! Runs the body of `function` within `contexes`, compiling it on its first call; see `RFunction::run`
+    let chunk = function
+        .compiled
+        .get_or_init(|| Rc::new(compile_function(function)))
+        .clone();
+    execute(&chunk.body, contexes, caller)
+}
+
+pub fn default_value<'a>(
+    function: &RFunction<'a>,
+    index: usize,
+    contexes: &[ContextRef<'a>],
+) -> VariableValue<'a> {
+    //! Evaluates the default value of the `index`-th argument of `function`
+    let chunk = function
+        .compiled
+        .get_or_init(|| Rc::new(compile_function(function)))
+        .clone();
+    match &chunk.defaults[index] {
+        Some(default) => evaluate_chunk(default, contexes.to_vec()),
+        None => VariableValue::Nil,
+    }
+}
+
+pub fn match_guard<'a>(arm: &RMatchArm<'a>, contexes: Vec<ContextRef<'a>>) -> VariableValue<'a> {
+    //! Evaluates the guard of `arm` within `contexes`, compiling the arm on its first use; an arm without a guard always holds
+    match &compiled_arm(arm).guard {
+        Some(guard) => evaluate_chunk(guard, contexes),
+        None => VariableValue::Boolean(true),
+    }
+}
+
+pub fn match_body<'a>(arm: &RMatchArm<'a>, contexes: Vec<ContextRef<'a>>) -> VariableValue<'a> {
+    //! Evaluates the body of `arm` within `contexes`, compiling the arm on its first use
+    evaluate_chunk(&compiled_arm(arm).body, contexes)
+}
+
+fn compiled_arm<'a>(arm: &RMatchArm<'a>) -> Rc<MatchArmChunk<'a>> {
+    arm.compiled.get_or_init(|| Rc::new(compile_match_arm(arm))).clone()
+}
+
+pub fn evaluate<'a>(
+    ast: RASTRef<'a>,
+    location: Location<'a>,
+    contexes: Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    //! Compiles and evaluates a block within `contexes`, like `interpreter::interprete` does
+    let mut compiler = Compiler::new(Vec::new());
+    compiler.scope(ast.clone(), location.clone());
+    compiler.emit(Op::Return, location);
+    evaluate_chunk(&compiler.finish(), contexes)
+}
+
+pub fn evaluate_instruction<'a>(
+    ast: &RASTRef<'a>,
+    instruction: &RASTNode<'a>,
+    location: Location<'a>,
+    contexes: Vec<ContextRef<'a>>,
+) -> VariableValue<'a> {
+    //! Compiles and evaluates an instruction of `ast` within `contexes`, whose last context is that of `ast`; see `interpreter::interprete_instruction`
+    let mut compiler = Compiler::new(vec![ast.clone()]);
+    compiler.instruction(instruction, location.clone());
+    compiler.emit(Op::Return, location);
+    evaluate_chunk(&compiler.finish(), contexes)
+}
+
+pub fn evaluate_defaults<'a>(st: &RStructRef<'a>, contexes: Vec<ContextRef<'a>>) {
+    //! Evaluates the `let` declarations of the body of `st` within `contexes`, whose last context is that of the body, compiling them on the first instantiation; see `interpreter::instance::instantiate`
+    let chunk = st.borrow().defaults.get_or_init(|| Rc::new(compile_defaults(st))).clone();
+    if !chunk.ops.is_empty() {
+        evaluate_chunk(&chunk, contexes);
+    }
+}
+
+pub fn interprete_interpretation<'a>(interpretation: &RInterpretation<'a>, contexes: Vec<ContextRef<'a>>) {
+    //! Evaluates the body of `interpretation` within `contexes`, whose only context declares `from` and `to`, compiling it on its first use; see `interpreter::interpretation::interprete_interpretation`
+    let chunk = interpretation.compiled.get_or_init(|| Rc::new(compile_interpretation(interpretation))).clone();
+    evaluate_chunk(&chunk, contexes);
+}
+
+fn evaluate_chunk<'a>(chunk: &Chunk<'a>, contexes: Vec<ContextRef<'a>>) -> VariableValue<'a> {
+    match execute(chunk, contexes, None) {
+        Ok(value) => value,
+        Err(_) => unreachable!("Tail calls are only returned to a caller"),
+    }
+}
+
+/** Executes a chunk, until it returns or a control flow signal is raised (see `interpreter::signal`).
+    @param contexes - The context stack at the start of the chunk; scopes entered by the chunk are pushed onto it
+    @param caller - The contexes to which tail calls may be returned (see `interpreter::interprete_tail`); they are made right away if `None`
+**/
+pub fn execute<'a>(
+    chunk: &Chunk<'a>,
+    mut contexes: Vec<ContextRef<'a>>,
+    caller: Option<&Vec<ContextRef<'a>>>,
+) -> Result<VariableValue<'a>, Box<TailCall<'a>>> {
+    let mut stack: Vec<ExprValue<'a>> = Vec::new();
+    let mut marks: Vec<usize> = Vec::new();
+    let is_program = contexes.is_empty();
+
+    for (index, op) in chunk.ops.iter().enumerate() {
+        match op {
+            Op::Push(value) => stack.push(ExprValue::Value(value.clone())),
+            Op::Load { up, slot } => {
+                let value = contexes[contexes.len() - 1 - up].borrow().variables[*slot].clone();
+                stack.push(ExprValue::Value(value));
+            }
+            Op::LoadOuter { depth, ulid, slot } => {
+                let ctx = find_context(&contexes, *depth, *ulid, &chunk.locations[index]);
+                let value = ctx.borrow().variables[*slot].clone();
+                stack.push(ExprValue::Value(value));
+            }
+            Op::Store { up, slot } => {
+                let value = pop_value(&mut stack);
+                contexes[contexes.len() - 1 - up].borrow_mut().variables[*slot] = value;
+            }
+            Op::StoreOuter { depth, ulid, slot } => {
+                let value = pop_value(&mut stack);
+                let ctx = find_context(&contexes, *depth, *ulid, &chunk.locations[index]);
+                ctx.borrow_mut().variables[*slot] = value;
+            }
+            Op::Tuple(n) => {
+                let values = pop_values(&mut stack, *n);
+                stack.push(ExprValue::Value(VariableValue::Tuple(values)));
+            }
+            Op::Interpolation(n) => {
+                let values = pop_values(&mut stack, *n);
+                let string = values.iter().map(|value| value.to_string()).collect();
+                stack.push(ExprValue::Value(VariableValue::String(string)));
+            }
+            Op::Function(fun) => {
+                let names: Vec<String> =
+                    fun.borrow().closure.iter().map(|(name, _)| name.clone()).collect();
+                let values = pop_values(&mut stack, names.len());
+                stack.push(ExprValue::Value(VariableValue::Function(
+                    fun.clone(),
+                    names.into_iter().zip(values).collect(),
+                )));
+            }
+            Op::Enter { depth, ulid, size } => contexes.push(Rc::new(RefCell::new(
+                Context::new(*depth, *ulid, *size),
+            ))),
+            Op::Exit => {
+                contexes.pop();
+            }
+            Op::SetLast => {
+                let value = pop_value(&mut stack);
+                contexes.last().unwrap().borrow_mut().last_value = value;
+            }
+            Op::KeepLast => {
+                if let Some(ExprValue::Value(value)) = stack.last() {
+                    contexes.last().unwrap().borrow_mut().last_value = value.clone();
+                }
+            }
+            Op::PushLast => {
+                let value = contexes.last().unwrap().borrow().last_value.clone();
+                stack.push(ExprValue::Value(value));
+            }
+            Op::Call(pat, layout) => {
+                let (args, named) = pop_args(&mut stack, layout);
+                let location = chunk.locations[index].clone();
+                let value = pat.call_named(args, named, location, &contexes, vec![], None);
+                stack.push(ExprValue::Value(value));
+            }
+            Op::TailCall(pat, layout) => {
+                let (args, named) = pop_args(&mut stack, layout);
+                let location = chunk.locations[index].clone();
+                return match caller {
+                    Some(caller) if pat.is_tail_callable(caller) => Err(Box::new(TailCall {
+                        function: pat.clone(),
+                        args,
+                        named,
+                        closure: vec![],
+                        location,
+                    })),
+                    Some(caller) => pat.call_tail(args, named, location, &contexes, vec![], caller),
+                    None => Ok(pat.call_named(args, named, location, &contexes, vec![], None)),
+                };
+            }
+            Op::Member(name) => stack.push(ExprValue::Member(name.clone())),
+            Op::MethodCall(name, layout, methods) => {
+                let (args, named) = pop_args(&mut stack, layout);
+                stack.push(ExprValue::MethodCall(name.clone(), args, named, methods.clone()));
+            }
+            Op::Args(layout) => {
+                let (args, named) = pop_args(&mut stack, layout);
+                stack.push(ExprValue::Args(args, named));
+            }
+            Op::PartialTuple(mask) => {
+                let mut values = pop_values(&mut stack, mask.iter().filter(|x| **x).count())
+                    .into_iter();
+                let elements = mask
+                    .iter()
+                    .map(|given| match given {
+                        true => PartialValue::VariableValue(values.next().unwrap()),
+                        false => PartialValue::Void,
+                    })
+                    .collect();
+                stack.push(ExprValue::PartialTuple(elements));
+            }
+            Op::Operator(op) => {
+                execute_op(op, &mut stack, chunk.locations[index].clone(), &contexes)
+            }
+            Op::BeginExpr => marks.push(stack.len()),
+            Op::EndExpr => {
+                let mark = marks.pop().unwrap();
+                let value = if stack.len() > mark { stack.pop() } else { None };
+                stack.truncate(mark);
+                match value {
+                    Some(ExprValue::Value(value)) => stack.push(ExprValue::Value(value)),
+                    _ => panic!("interprete_expression_int(...) returned an empty array"),
+                }
+            }
+            Op::GetOld(name) => {
+                let old = match stack.last() {
+                    Some(ExprValue::Value(VariableValue::Instance(t, vars) | VariableValue::Super(_, t, vars))) => {
+                        instance::get_field(t, vars, name, chunk.locations[index].clone())
+                    }
+                    Some(ExprValue::Value(VariableValue::Type(t))) => {
+                        instance::get_static(t, name, chunk.locations[index].clone(), &contexes)
+                    }
+                    _ => panic!("Trying to set value on non-object"),
+                };
+                stack.push(ExprValue::Value(old));
+            }
+            Op::SetMember(name) => {
+                let value = pop_value(&mut stack);
+                let old = pop_value(&mut stack);
+                match pop_value(&mut stack) {
+                    VariableValue::Instance(t, vars) | VariableValue::Super(_, t, vars) => {
+                        instance::set_field(&t, &vars, name, value, chunk.locations[index].clone());
+                    }
+                    VariableValue::Type(t) => {
+                        instance::set_static(&t, name, value, chunk.locations[index].clone(), &contexes);
+                    }
+                    _ => panic!("Trying to set value on non-object"),
+                }
+                stack.push(ExprValue::Value(old));
+            }
+            Op::StructLiteral(st, names) => {
+                let values = pop_values(&mut stack, names.len());
+                let fields = names.iter().cloned().zip(values).collect();
+                stack.push(ExprValue::Value(instance::instantiate(
+                    st.clone(),
+                    fields,
+                    chunk.locations[index].clone(),
+                    &contexes,
+                )));
+            }
+            Op::Define(expr, member) => {
+                let value = pop_value(&mut stack);
+                let location = chunk.locations[index].clone();
+                stack.push(ExprValue::Value(assign::define(expr, member, value, location, &contexes)));
+            }
+            Op::Compound(target, op) => {
+                let value = pop_value(&mut stack);
+                let location = chunk.locations[index].clone();
+                stack.push(ExprValue::Value(assign::compound(target, *op, value, location, &contexes)));
+            }
+            Op::CompoundMember(name, op) => {
+                let value = pop_value(&mut stack);
+                let object = pop_value(&mut stack);
+                let location = chunk.locations[index].clone();
+                stack.push(ExprValue::Value(assign::compound_member(
+                    object, name, *op, value, location, &contexes,
+                )));
+            }
+            Op::Match(arms) => {
+                let value = pop_value(&mut stack);
+                let location = chunk.locations[index].clone();
+                stack.push(ExprValue::Value(matching::match_value(value, arms, location, &contexes)));
+            }
+            Op::Return => return Ok(pop_value(&mut stack)),
+        }
+
+        // the ops which may run code may raise a signal, which stops the chunk like `interpreter::interprete` does
+        let runs_code = matches!(
+            op,
+            Op::Call(_, _) | Op::Operator(_) | Op::Match(_) | Op::StructLiteral(_, _)
+        );
+        if runs_code && signal::is_raised(&contexes) {
+            return Ok(if is_program {
+                signal::finish(VariableValue::Nil, &contexes)
+            } else {
+                VariableValue::Nil
+            });
+        }
+    }
+
+    panic!("Expected chunk to end with a return instruction");
+}
+
+fn find_context<'a, 'b>(
+    contexes: &'b [ContextRef<'a>],
+    depth: usize,
+    ulid: u128,
+    location: &Location<'a>,
+) -> &'b ContextRef<'a> {
+    //! Looks up the innermost context at `depth` with the ulid `ulid`
+    contexes
+        .iter()
+        .rev()
+        .find(|ctx| ctx.borrow().depth == depth && ctx.borrow().ulid == ulid)
+        .unwrap_or_else(|| {
+            CompError::new(
+                1,
+                format!("Couldn't find context at depth {}!", depth),
+                location.clone().into(),
+            )
+            .print_and_exit()
+        })
+}
+
+fn pop_value<'a>(stack: &mut Vec<ExprValue<'a>>) -> VariableValue<'a> {
+    match stack.pop() {
+        Some(ExprValue::Value(value)) => value,
+        _ => panic!("Expected a value on top of the VM's stack"),
+    }
+}
+
+fn pop_values<'a>(stack: &mut Vec<ExprValue<'a>>, n: usize) -> Vec<VariableValue<'a>> {
+    //! Pops the `n` values on top of the stack, in the order in which they were pushed
+    let values = stack.split_off(stack.len() - n);
+    values
+        .into_iter()
+        .map(|value| match value {
+            ExprValue::Value(value) => value,
+            _ => panic!("Expected a value on the VM's stack"),
+        })
+        .collect()
+}
+
+fn pop_args<'a>(
+    stack: &mut Vec<ExprValue<'a>>,
+    layout: &ArgLayout,
+) -> (Vec<VariableValue<'a>>, Vec<(String, VariableValue<'a>)>) {
+    //! Pops the arguments of a call, returning its positional and its named arguments
+    let mut args = Vec::new();
+    let mut named = Vec::new();
+    for (value, name) in pop_values(stack, layout.len()).into_iter().zip(layout) {
+        match name {
+            Some(name) => named.push((name.clone(), value)),
+            None => args.push(value),
+        }
+    }
+    (args, named)
+}
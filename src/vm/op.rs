@@ -0,0 +1,92 @@
+use super::*;
+
+/// Where the arguments of a call come from, in order: `None` for a positional argument and `Some(name)` for a named one
+pub type ArgLayout = Vec<Option<String>>;
+
+/** A single bytecode instruction.
+    Every instruction pops its operands from the VM's stack and pushes its result back onto it.
+**/
+#[derive(Debug)]
+pub enum Op<'a> {
+    /// Pushes a constant
+    Push(VariableValue<'a>),
+    /// Pushes the variable at `slot` of the context `up` scopes above the innermost one
+    Load { up: usize, slot: usize },
+    /// Pushes a variable declared outside of the chunk; its context is looked up by depth and ulid
+    LoadOuter { depth: usize, ulid: u128, slot: usize },
+    /// Pops a value into the variable at `slot` of the context `up` scopes above the innermost one
+    Store { up: usize, slot: usize },
+    /// Pops a value into a variable declared outside of the chunk
+    StoreOuter { depth: usize, ulid: u128, slot: usize },
+    /// Pops `n` values into a tuple
+    Tuple(usize),
+    /// Pops `n` values and joins them into a string, see `RASTNode::Interpolation`
+    Interpolation(usize),
+    /// Pops the values of the function's closure and pushes the function
+    Function(RFunRef<'a>),
+    /// Enters a new scope, holding `size` variables
+    Enter { depth: usize, ulid: u128, size: usize },
+    /// Exits the innermost scope
+    Exit,
+    /// Pops a value into the `last_value` of the innermost scope
+    SetLast,
+    /// Copies the top value into the `last_value` of the innermost scope
+    KeepLast,
+    /// Pushes the `last_value` of the innermost scope
+    PushLast,
+    /// Pops the arguments and calls the pattern
+    Call(RPatRef<'a>, ArgLayout),
+    /// Pops the arguments and calls the pattern in tail position, ending the chunk
+    TailCall(RPatRef<'a>, ArgLayout),
+    /// Pushes `ExprValue::Member`
+    Member(String),
+    /// Pops the arguments and pushes `ExprValue::MethodCall`
+    MethodCall(String, ArgLayout, PrimitiveMethodsRef<'a>),
+    /// Pops the arguments and pushes `ExprValue::Args`
+    Args(ArgLayout),
+    /// Pops the non-void elements (`true` in the mask) and pushes `ExprValue::PartialTuple`
+    PartialTuple(Vec<bool>),
+    /// Executes an operator, see `interpreter::execute_op`
+    Operator(Operator),
+    /// Marks the start of an expression
+    BeginExpr,
+    /// Pops the value of the expression and discards what remains of it on the stack
+    EndExpr,
+    /// Pushes the current value of a member of the instance on top of the stack
+    GetOld(String),
+    /// Pops a value, the member's previous value and the instance, sets the member and pushes its previous value
+    SetMember(String),
+    /// Pops a value, stores it in a tuple element or destructures it, and pushes the previous value(s), see `interpreter::assign::define`
+    Define(RExpression<'a>, RDefineMember),
+    /// Pops a value, updates the variable or tuple element with it and pushes its previous value, see `interpreter::assign::compound`
+    Compound(RDefineTarget<'a>, Operator),
+    /// Pops a value and the instance, updates the member with it and pushes its previous value
+    CompoundMember(String, Operator),
+    /// Pops the values of the given fields and pushes a new instance of the struct, see `interpreter::instance::instantiate`
+    StructLiteral(RStructRef<'a>, Vec<String>),
+    /// Pops a value and pushes the value of the first arm matching it, see `interpreter::matching::match_value`
+    Match(Rc<Vec<RMatchArm<'a>>>),
+    /// Pops a value and returns it
+    Return,
+}
+
+/// A sequence of instructions, along with their location in the source code
+#[derive(Debug, Default)]
+pub struct Chunk<'a> {
+    pub ops: Vec<Op<'a>>,
+    pub locations: Vec<Location<'a>>,
+}
+
+/// The compiled guard and body of an arm of a `#match`
+#[derive(Debug)]
+pub struct MatchArmChunk<'a> {
+    pub guard: Option<Chunk<'a>>,
+    pub body: Chunk<'a>,
+}
+
+/// The compiled body of a function, along with the compiled default values of its arguments
+#[derive(Debug)]
+pub struct FunctionChunk<'a> {
+    pub body: Chunk<'a>,
+    pub defaults: Vec<Option<Chunk<'a>>>,
+}
@@ -30,3 +30,123 @@ fn blocks() {
         test::execute(test::compile(&src))
     );
 }
+
+#[test]
+fn keywords() {
+    test::init_testenv();
+    let src = test::load("test/basic/keywords.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![VariableValue::Number(3.0), VariableValue::Number(6.0)]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+fn strings() {
+    test::init_testenv();
+    let src = test::load("test/basic/strings.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::String("a\tb\r\0H\u{e9} {x} \"\\".to_string()),
+            VariableValue::String("x + 1 = 4, hello world!".to_string()),
+            VariableValue::String("first \"line\"\nsecond line, 6".to_string()),
+            VariableValue::String("} and {\"}".to_string()),
+            VariableValue::String("t = (1, 2), double = [function 'double]".to_string()),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid string interpolation")]
+fn strings_interpolation_error() {
+    test::init_testenv();
+    let src = test::load("test/basic/strings_interpolation_error.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn comments() {
+    test::init_testenv();
+    let src = test::load("test/basic/comments.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::String("Swaps the two members of a tuple\nReturns (b, a)".to_string()),
+            VariableValue::String("A point in space".to_string()),
+            VariableValue::String("Doubles a number".to_string()),
+            VariableValue::Nil,
+            VariableValue::Tuple(vec![VariableValue::Number(4.0), VariableValue::Number(3.0)]),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unterminated block comment")]
+fn comments_unterminated() {
+    test::init_testenv();
+    let src = test::load("test/basic/comments_unterminated.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn constants() {
+    test::init_testenv();
+    let src = test::load("test/basic/constants.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(9.0),
+            VariableValue::Number(1.0),
+            VariableValue::Number(4.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign to constant x")]
+fn constants_assign() {
+    test::init_testenv();
+    let src = test::load("test/basic/constants_assign.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign to constant t")]
+fn constants_element() {
+    test::init_testenv();
+    let src = test::load("test/basic/constants_element.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign to constant a")]
+fn constants_destructuring() {
+    test::init_testenv();
+    let src = test::load("test/basic/constants_destructuring.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign to constant x")]
+fn constants_redeclare() {
+    test::init_testenv();
+    let src = test::load("test/basic/constants_redeclare.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot declare constant y")]
+fn constants_redeclare_variable() {
+    test::init_testenv();
+    let src = test::load("test/basic/constants_redeclare_variable.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign to constant count")]
+fn constants_ref() {
+    test::init_testenv();
+    let src = test::load("test/basic/constants_ref.patpat");
+    test::execute(test::compile(&src));
+}
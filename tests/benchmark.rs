@@ -0,0 +1,49 @@
+//! Benchmarks, which are ignored by default.
+//! Run them with `cargo test --release --test benchmark -- --ignored --show-output`
+
+use patpat::test;
+use std::time::Instant;
+
+const RUNS: u32 = 20000;
+
+fn bench(name: &str, path: &str, use_vm: bool) {
+    test::init_testenv();
+    let src = test::load(path);
+    let program = test::compile(&src);
+
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        if use_vm {
+            test::execute_vm(program.clone());
+        } else {
+            test::execute(program.clone());
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{}: {} runs in {:?} ({:?} per run)",
+        name,
+        RUNS,
+        elapsed,
+        elapsed / RUNS
+    );
+}
+
+#[test]
+#[ignore]
+fn fibonacci() {
+    bench("fibonacci (interpreter)", "examples/fibonacci.patpat", false);
+    bench("fibonacci (vm)", "examples/fibonacci.patpat", true);
+}
+
+/** Unlike fibonacci, whose time goes to `#println`, this workload is dominated by variable accesses and context creation.
+    Averaged over 3 release runs, the `HashMap`-based contexts took ~410µs per run in the interpreter and ~280µs in the VM;
+    the slot-based ones take ~340µs and ~270µs.
+**/
+#[test]
+#[ignore]
+fn variables() {
+    bench("variables (interpreter)", "test/benchmark/variables.patpat", false);
+    bench("variables (vm)", "test/benchmark/variables.patpat", true);
+}
@@ -0,0 +1,46 @@
+use patpat::test;
+
+#[test]
+fn instance() {
+    test::init_testenv();
+    let src = test::load("test/debug/instance.patpat");
+    assert_eq!(
+        concat!(
+            "(\n",
+            "  Point {\n",
+            "    x: 1,\n",
+            "    y: 2\n",
+            "  },\n",
+            "  (3, \"four\"),\n",
+            "  nil\n",
+            ")",
+        ),
+        format!("{}", test::execute(test::compile(&src)).pretty())
+    );
+}
+
+#[test]
+fn cycle() {
+    test::init_testenv();
+    let src = test::load("test/debug/cycle.patpat");
+    assert_eq!(
+        concat!(
+            "Node {\n",
+            "  next: Node {\n",
+            "    next: Node { <cycle> }\n",
+            "  }\n",
+            "}",
+        ),
+        format!("{}", test::execute(test::compile(&src)).pretty())
+    );
+}
+
+#[test]
+fn function() {
+    test::init_testenv();
+    let src = test::load("test/debug/function.patpat");
+    assert_eq!(
+        "([function add, 2 arguments, declared at test/debug/function.patpat:0:0], \"quote: \\\"\")",
+        format!("{}", test::execute(test::compile(&src)).pretty())
+    );
+}
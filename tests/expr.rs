@@ -67,3 +67,55 @@ fn shortcircuiting() {
         test::execute(test::compile(&src))
     );
 }
+
+#[test]
+fn numbers() {
+    test::init_testenv();
+    let src = test::load("test/expr/numbers.patpat");
+    assert_eq!(
+        VariableValue::Tuple(
+            vec![255.0, 10.0, 15.0, -16.0, 1000000.0, 65535.0, 1000.0, 0.0025, 150.0, -10.25]
+                .into_iter()
+                .map(VariableValue::Number)
+                .collect()
+        ),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid digit 'G' in hexadecimal number literal")]
+fn numbers_invalid_digit() {
+    test::init_testenv();
+    let src = test::load("test/expr/numbers_invalid_digit.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Digit separators ('_') must be placed between two digits")]
+fn numbers_separator() {
+    test::init_testenv();
+    let src = test::load("test/expr/numbers_separator.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn compound() {
+    test::init_testenv();
+    let src = test::load("test/expr/compound.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(3.0),
+            VariableValue::Number(2.0),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(0.0),
+                VariableValue::Tuple(vec![VariableValue::Number(12.0), VariableValue::Number(3.0)]),
+            ]),
+            VariableValue::Number(6.0),
+            VariableValue::Tuple(vec![VariableValue::Number(1.0), VariableValue::Number(20.0)]),
+            VariableValue::String("compound definition".to_string()),
+        ]),
+        test::execute(test::compile(&src))
+    );
+    assert_eq!("String(\"get\")\nString(\"get\")\n", test::get_logs());
+}
@@ -122,3 +122,341 @@ fn error_ref() {
     let src = test::load("test/patterns/error_ref.patpat");
     test::execute(test::compile(&src));
 }
+
+#[test]
+fn arity() {
+    test::init_testenv();
+    let src = test::load("test/patterns/arity.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(2.0),
+            VariableValue::Number(1.0),
+            VariableValue::Number(1.0),
+            VariableValue::Number(2.0),
+            VariableValue::Nil,
+            VariableValue::Nil,
+            VariableValue::Number(1.0),
+            VariableValue::Nil,
+            VariableValue::Number(2.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+fn names() {
+    test::init_testenv();
+    let src = test::load("test/patterns/names.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::String(String::from("[function id]")),
+            VariableValue::String(String::from("[function 'add]")),
+            VariableValue::String(String::from("[function <anonymous function>]")),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+fn if_function() {
+    test::init_testenv();
+    let src = test::load("test/patterns/if_function.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![VariableValue::Number(8.0), VariableValue::Number(3.0)]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+fn defaults() {
+    test::init_testenv();
+    let src = test::load("test/patterns/defaults.patpat");
+    let range = |from: f64, to: f64, step: f64| {
+        VariableValue::Tuple(vec![
+            VariableValue::Number(from),
+            VariableValue::Number(to),
+            VariableValue::Number(step),
+        ])
+    };
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            range(0.0, 10.0, 1.0),
+            range(0.0, 5.0, 1.0),
+            range(0.0, 5.0, 2.0),
+            VariableValue::Number(2.0),
+            range(5.0, 6.0, 1.0),
+            range(5.0, 6.0, 2.0),
+            range(5.0, 20.0, 1.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Mismatching number of arguments: expected 1 to 2, got 3.")]
+fn defaults_nargs_panic() {
+    test::init_testenv();
+    let src = test::load("test/patterns/defaults_nargs_panic.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn rest() {
+    test::init_testenv();
+    let src = test::load("test/patterns/rest.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Tuple(vec![VariableValue::Number(1.0), VariableValue::Tuple(vec![])]),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(1.0),
+                VariableValue::Tuple(vec![VariableValue::Number(2.0), VariableValue::Number(3.0)]),
+            ]),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(0.0),
+                VariableValue::Tuple(vec![VariableValue::Number(4.0), VariableValue::Number(5.0)]),
+            ]),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+fn named_args() {
+    test::init_testenv();
+    let src = test::load("test/patterns/named_args.patpat");
+    let numbers = |vec: Vec<f64>| {
+        VariableValue::Tuple(vec.into_iter().map(VariableValue::Number).collect())
+    };
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            numbers(vec![1.0, 11.0, 2.0]),
+            numbers(vec![0.0, 10.0, 5.0]),
+            VariableValue::Number(2.0),
+            VariableValue::Number(2.0),
+            numbers(vec![0.0, 2.0]),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unknown named argument length in call to 'range")]
+fn named_args_unknown() {
+    test::init_testenv();
+    let src = test::load("test/patterns/named_args_unknown.patpat");
+    test::compile(&src);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate named argument step")]
+fn named_args_duplicate() {
+    test::init_testenv();
+    let src = test::load("test/patterns/named_args_duplicate.patpat");
+    test::compile(&src);
+}
+
+#[test]
+#[should_panic(expected = "Unknown named argument c in call to sub")]
+fn named_args_runtime() {
+    test::init_testenv();
+    let src = test::load("test/patterns/named_args_runtime.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn tail_call() {
+    test::init_testenv();
+    test::set_max_call_depth(32);
+    let src = test::load("test/patterns/tail_call.patpat");
+    assert_eq!(
+        VariableValue::Number(500500.0),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Stack overflow")]
+fn stack_overflow() {
+    test::init_testenv();
+    test::set_max_call_depth(32);
+    let src = test::load("test/patterns/stack_overflow.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn r#match() {
+    test::init_testenv();
+    let src = test::load("test/patterns/match.patpat");
+    let expected = [
+        "zero",
+        "greeting",
+        "yes",
+        "starts with zero",
+        "pair of 2",
+        "pair",
+        "circle of radius 1",
+        "square of side 2",
+        "rect 2x3",
+        "empty",
+        "point on the y axis at 4",
+        "point 1, 2",
+        "big",
+        "other",
+    ];
+    assert_eq!(
+        VariableValue::Tuple(
+            expected
+                .iter()
+                .map(|x| VariableValue::String(String::from(*x)))
+                .collect()
+        ),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unmatched value in #match: 3")]
+fn match_unmatched() {
+    test::init_testenv();
+    let src = test::load("test/patterns/match_unmatched.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Unknown variable b")]
+fn match_scope() {
+    test::init_testenv();
+    let src = test::load("test/patterns/match_scope.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn r#return() {
+    test::init_testenv();
+    let src = test::load("test/patterns/return.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(2.0),
+            VariableValue::Number(-1.0),
+            VariableValue::Number(8.0),
+            VariableValue::Number(10.0),
+            VariableValue::Number(300.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+    assert_eq!("", test::get_logs());
+}
+
+#[test]
+fn break_continue() {
+    test::init_testenv();
+    let src = test::load("test/patterns/break_continue.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(8.0),
+            VariableValue::Number(25.0),
+            VariableValue::String(String::from("three")),
+            VariableValue::Number(100.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot #break() outside of a loop")]
+fn break_outside() {
+    test::init_testenv();
+    let src = test::load("test/patterns/break_outside.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn range() {
+    test::init_testenv();
+    let src = test::load("test/patterns/range.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::String(" 3 2 1 0 -1".to_string()),
+            VariableValue::String(" 0 0.25 0.5 0.75 1".to_string()),
+            VariableValue::Number(30.0),
+            VariableValue::String("10..0 by -2".to_string()),
+            VariableValue::String("1..4".to_string()),
+            VariableValue::Nil,
+            VariableValue::Nil,
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid range from 1 to 5 by 0")]
+fn range_step() {
+    test::init_testenv();
+    let src = test::load("test/patterns/range_step.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn pipeline() {
+    test::init_testenv();
+    let src = test::load("test/patterns/pipeline.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(7.0),
+            VariableValue::Number(7.0),
+            VariableValue::Number(-7.0),
+            VariableValue::Number(22.0),
+            VariableValue::Number(6.0),
+            VariableValue::Number(1.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Expected a function as operand of |>, got 4")]
+fn pipeline_error() {
+    test::init_testenv();
+    let src = test::load("test/patterns/pipeline_error.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn overloads() {
+    test::init_testenv();
+    let src = test::load("test/patterns/overloads.patpat");
+    let string = |x: &str| VariableValue::String(String::from(x));
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Tuple(vec![string("hello"), string("hello you"), string("hi you"), string("hey you")]),
+            VariableValue::Tuple(vec![string("value"), string("shape of area 4"), string("circle")]),
+            VariableValue::Number(5050.0),
+            VariableValue::Number(6.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Ambiguous declaration of 'f: it accepts the same arguments as a previous one")]
+fn overloads_ambiguous() {
+    test::init_testenv();
+    let src = test::load("test/patterns/overloads_ambiguous.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Ambiguous call to 'move: several declarations accept the arguments")]
+fn overloads_ambiguous_call() {
+    test::init_testenv();
+    let src = test::load("test/patterns/overloads_ambiguous_call.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "No declaration of 'norm accepts the arguments (1)")]
+fn overloads_no_match() {
+    test::init_testenv();
+    let src = test::load("test/patterns/overloads_no_match.patpat");
+    test::execute(test::compile(&src));
+}
@@ -0,0 +1,321 @@
+use patpat::interpreter::VariableValue;
+use patpat::test;
+
+#[test]
+fn defaults() {
+    test::init_testenv();
+    let src = test::load("test/structs/defaults.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Tuple(vec![
+                VariableValue::Number(5.0),
+                VariableValue::Number(11.0),
+                VariableValue::Nil
+            ]),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(0.0),
+                VariableValue::Number(2.0),
+                VariableValue::String(String::from("b"))
+            ]),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(7.0),
+                VariableValue::Number(11.0),
+                VariableValue::Nil
+            ]),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unknown field z in struct Point")]
+fn unknown_field_get() {
+    test::init_testenv();
+    let src = test::load("test/structs/unknown_field_get.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Unknown field z in struct Point")]
+fn unknown_field_set() {
+    test::init_testenv();
+    let src = test::load("test/structs/unknown_field_set.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Unknown field z in struct Point")]
+fn unknown_field_literal() {
+    test::init_testenv();
+    let src = test::load("test/structs/unknown_field_literal.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn statics() {
+    test::init_testenv();
+    let src = test::load("test/structs/statics.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(0.5),
+            VariableValue::Number(0.25),
+            VariableValue::Number(4.0),
+            VariableValue::Number(2.0),
+            VariableValue::Number(2.0),
+            VariableValue::Number(0.0),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unknown static member x in struct Point")]
+fn statics_field() {
+    test::init_testenv();
+    let src = test::load("test/structs/statics_field.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Unknown field count in struct Counter")]
+fn statics_instance() {
+    test::init_testenv();
+    let src = test::load("test/structs/statics_instance.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "count is declared both as a field and as a static member of the struct")]
+fn statics_duplicate() {
+    test::init_testenv();
+    let src = test::load("test/structs/statics_duplicate.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot call method 'get_x on type Point")]
+fn statics_self() {
+    test::init_testenv();
+    let src = test::load("test/structs/statics_self.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Trying to set value on non-object")]
+fn set_non_object() {
+    test::init_testenv();
+    let src = test::load("test/structs/set_non_object.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn statics_overload() {
+    test::init_testenv();
+    let src = test::load("test/structs/statics_overload.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![VariableValue::Number(0.0), VariableValue::Number(5.0)]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot call method 'make on type Point")]
+fn statics_overload_self() {
+    test::init_testenv();
+    let src = test::load("test/structs/statics_overload_self.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn extends() {
+    test::init_testenv();
+    let src = test::load("test/structs/extends.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(9.0),
+            VariableValue::String(String::from("square with 4 sides and area 9!")),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(4.0),
+                VariableValue::String(String::from("square"))
+            ]),
+            VariableValue::String(String::from("shape with 0 sides and area 0")),
+            VariableValue::Number(9.0),
+            VariableValue::String(String::from("square with 4 sides and area 9!")),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(9.0),
+                VariableValue::String(String::from("square"))
+            ]),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "#super() can only be used within the methods of a struct extending another one")]
+fn extends_super_error() {
+    test::init_testenv();
+    let src = test::load("test/structs/extends_super_error.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "#super() can only be used within the methods of a struct extending another one")]
+fn extends_super_toplevel() {
+    test::init_testenv();
+    let src = test::load("test/structs/extends_super_toplevel.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cyclic inheritance")]
+fn extends_cycle() {
+    test::init_testenv();
+    let src = test::load("test/structs/extends_cycle.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn interfaces() {
+    test::init_testenv();
+    let src = test::load("test/structs/interfaces.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::String(String::from("rect: 6")),
+            VariableValue::String(String::from("square: 4")),
+            VariableValue::String(String::from("circle: 12")),
+            VariableValue::String(String::from("square")),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Struct Circle does not implement interface Shape")]
+fn interfaces_missing() {
+    test::init_testenv();
+    let src = test::load("test/structs/interfaces_missing.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "expected an instance of Shape for argument shape of 'area_of, got [Point instance]")]
+fn interfaces_argtype() {
+    test::init_testenv();
+    let src = test::load("test/structs/interfaces_argtype.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot instantiate Shape: it is an interface")]
+fn interfaces_instance() {
+    test::init_testenv();
+    let src = test::load("test/structs/interfaces_instance.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn enums() {
+    test::init_testenv();
+    let src = test::load("test/structs/enums.patpat");
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Number(2.0),
+            VariableValue::Tuple(vec![VariableValue::Number(2.0), VariableValue::Number(3.0)]),
+            VariableValue::String(String::from("Rect(2, 3)")),
+            VariableValue::String(String::from("Empty")),
+            VariableValue::Boolean(true),
+            VariableValue::Boolean(false),
+            VariableValue::Boolean(true),
+            VariableValue::String(String::from("shape: Circle(2)")),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot instantiate Shape: it is an enum, use one of its variants (Circle, Empty) instead")]
+fn enums_instance() {
+    test::init_testenv();
+    let src = test::load("test/structs/enums_instance.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Mismatching number of fields for variant Rect: expected 2, got 1")]
+fn enums_fields() {
+    test::init_testenv();
+    let src = test::load("test/structs/enums_fields.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Type Err is already declared in this scope")]
+fn enums_collision() {
+    test::init_testenv();
+    let src = test::load("test/structs/enums_collision.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Type Circle is already declared in this scope")]
+fn enums_shadow() {
+    test::init_testenv();
+    let src = test::load("test/structs/enums_shadow.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+fn primitive_methods() {
+    test::init_testenv();
+    let src = test::load("test/structs/primitive_methods.patpat");
+    let string = |x: &str| VariableValue::String(String::from(x));
+    let numbers = |xs: &[f64]| VariableValue::Tuple(xs.iter().map(|x| VariableValue::Number(*x)).collect());
+    assert_eq!(
+        VariableValue::Tuple(vec![
+            VariableValue::Tuple(vec![
+                VariableValue::Number(3.0),
+                string("hi"),
+                VariableValue::Tuple(vec![string("a"), string("b")]),
+                string("abab"),
+                VariableValue::Boolean(true),
+            ]),
+            VariableValue::Tuple(vec![
+                VariableValue::Number(9.0),
+                VariableValue::Number(3.0),
+                VariableValue::Number(2.0),
+                VariableValue::Number(2.0),
+                VariableValue::Boolean(false),
+            ]),
+            VariableValue::Tuple(vec![
+                numbers(&[6.0, 2.0, 4.0]),
+                numbers(&[3.0, 2.0]),
+                VariableValue::Number(6.0),
+                numbers(&[2.0, 1.0, 3.0]),
+                VariableValue::Number(3.0),
+            ]),
+            VariableValue::Tuple(vec![
+                numbers(&[1.0, 2.0, 3.0]),
+                VariableValue::Boolean(true),
+                numbers(&[1.0, 4.0, 9.0, 16.0]),
+            ]),
+            VariableValue::Tuple(vec![string("HEY!"), VariableValue::Number(42.0), string("abs")]),
+        ]),
+        test::execute(test::compile(&src))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot find method 'len of type Number")]
+fn primitive_methods_unknown() {
+    test::init_testenv();
+    let src = test::load("test/structs/primitive_methods_unknown.patpat");
+    test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot extend Point: it is not a primitive type")]
+fn primitive_methods_extends() {
+    test::init_testenv();
+    let src = test::load("test/structs/primitive_methods_extends.patpat");
+    test::execute(test::compile(&src));
+}
@@ -42,3 +42,59 @@ fn pop() {
       test::execute(test::compile(&src))
   );
 }
+
+#[test]
+fn destructuring() {
+  test::init_testenv();
+  let src = test::load("test/tuples/destructuring.patpat");
+  assert_eq!(
+      VariableValue::Tuple(vec![
+          VariableValue::Number(2.0),
+          VariableValue::Number(1.0),
+          VariableValue::Number(3.0),
+          VariableValue::Number(4.0),
+          VariableValue::Number(6.0),
+      ]),
+      test::execute(test::compile(&src))
+  );
+}
+
+#[test]
+fn assign() {
+  test::init_testenv();
+  let src = test::load("test/tuples/assign.patpat");
+  assert_eq!(
+      VariableValue::Tuple(vec![
+          VariableValue::Tuple(vec![
+              VariableValue::Number(4.0),
+              VariableValue::Tuple(vec![VariableValue::Number(5.0), VariableValue::Number(3.0)]),
+          ]),
+          VariableValue::Tuple(vec![VariableValue::Number(6.0), VariableValue::Number(8.0)]),
+      ]),
+      test::execute(test::compile(&src))
+  );
+}
+
+#[test]
+#[should_panic(expected = "Mismatching tuple length in destructuring")]
+fn destructuring_length() {
+  test::init_testenv();
+  let src = test::load("test/tuples/destructuring_length.patpat");
+  test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Incomplete variable definition")]
+fn destructuring_incomplete() {
+  test::init_testenv();
+  let src = test::load("test/tuples/destructuring_incomplete.patpat");
+  test::execute(test::compile(&src));
+}
+
+#[test]
+#[should_panic(expected = "Cannot assign to an element of a temporary tuple")]
+fn assign_temporary() {
+  test::init_testenv();
+  let src = test::load("test/tuples/assign_temporary.patpat");
+  test::execute(test::compile(&src));
+}
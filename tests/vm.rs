@@ -0,0 +1,58 @@
+use patpat::test;
+use std::fs;
+use std::thread;
+
+/// Runs the program at `path` within its own thread, returning its result (or the error it panicked with) and its logs
+fn run(path: String, use_vm: bool) -> (String, String) {
+    let res = thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(move || {
+            test::init_testenv();
+            test::set_max_call_depth(256);
+            let src = test::load(&path);
+            let program = test::compile(&src);
+            let value = if use_vm {
+                test::execute_vm(program)
+            } else {
+                test::execute(program)
+            };
+            (format!("{}", value.pretty()), test::get_logs())
+        })
+        .unwrap()
+        .join();
+
+    match res {
+        Ok(res) => res,
+        Err(e) => match e.downcast::<String>() {
+            Ok(msg) => (*msg, String::new()),
+            Err(e) => (e.downcast_ref::<&str>().unwrap_or(&"").to_string(), String::new()),
+        },
+    }
+}
+
+fn collect(dir: &str, files: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect(path.to_str().unwrap(), files);
+        } else if path.extension().is_some_and(|x| x == "patpat") {
+            files.push(path.to_str().unwrap().to_string());
+        }
+    }
+}
+
+#[test]
+fn same_as_interpreter() {
+    let mut files = Vec::new();
+    collect("test", &mut files);
+    files.sort();
+
+    for path in files {
+        assert_eq!(
+            run(path.clone(), false),
+            run(path.clone(), true),
+            "VM and interpreter disagree on {}",
+            path
+        );
+    }
+}